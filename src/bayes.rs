@@ -0,0 +1,377 @@
+use array::Array;
+use num_traits::Float;
+use num_traits::NumCast;
+use num_traits::One;
+use num_traits::Zero;
+
+use crate::global::spread;
+use crate::global::Phase;
+use crate::linalg;
+use crate::minimizer::MaxIter;
+use crate::minimizer::Minimizer;
+use crate::minimizer::Output;
+use crate::minimizer::Result;
+use crate::minimizer::Termination;
+use crate::objective::Move;
+use crate::objective::Objective;
+use crate::rng::Rng;
+use crate::simplex::Pair;
+use crate::vector::Vector;
+
+use std::cmp::Ordering;
+
+/// Candidate length scales of the kernel, in the normalized box.
+const LENGTH_SCALES: [f64; 6] = [0.05, 0.1, 0.2, 0.3, 0.5, 1.0];
+
+/// Kernel diagonal jitter, relative to the signal variance.
+const NUGGET: f64 = 1e-6;
+
+/// A structure that holds all the Bayesian optimization parameters.
+///
+/// It fits a Gaussian process on the samples, with a squared exponential
+/// kernel, and samples next the maximum of the expected improvement found
+/// by the Nelder-Mead minimizer, for functions too expensive to spend
+/// more than a few dozen evaluations on.
+#[derive(Debug)]
+pub struct BayesianOptimization<A: Array> {
+    // Initial samples parameter, per dimension
+    init: usize,
+
+    // Exploration margin parameter, relative to the value spread
+    xi: A::Item,
+
+    // Iterations parameter
+    max_iter: MaxIter,
+
+    // Function evaluation parameter
+    max_fev: Option<usize>,
+
+    // Random generator parameter
+    seed: u64,
+}
+
+impl<A: Array> Default for BayesianOptimization<A>
+where
+    A::Item: Float,
+{
+    fn default() -> BayesianOptimization<A> {
+        BayesianOptimization {
+            init: 5,
+            xi: <A::Item as NumCast>::from(0.01).unwrap(),
+            max_iter: MaxIter::PerDimension(15),
+            max_fev: None,
+            seed: 0,
+        }
+    }
+}
+
+/// Gaussian process regression of the values on the normalized samples.
+struct Process<T> {
+    us: Vec<Vec<T>>,
+    // Cholesky factor of the kernel matrix
+    chol: Vec<T>,
+    alpha: Vec<T>,
+    length: T,
+    variance: T,
+    mean: T,
+    scale: T,
+}
+
+impl<T: Float> Process<T> {
+    /// Fits the process, the length scale maximizing the likelihood.
+    fn fit(us: &[Vec<T>], fs: &[T]) -> Option<Process<T>> {
+        let n = fs.len();
+        let count = <T as NumCast>::from(n).unwrap();
+        let mean = fs.iter().fold(T::zero(), |acc, &f| acc + f) / count;
+        let scale = (fs
+            .iter()
+            .fold(T::zero(), |acc, &f| acc + (f - mean).powi(2))
+            / count)
+            .sqrt()
+            .max(T::epsilon());
+        let ys: Vec<T> = fs.iter().map(|&f| (f - mean) / scale).collect();
+
+        let mut best: Option<(T, Process<T>)> = None;
+        for &length in &LENGTH_SCALES {
+            let length = <T as NumCast>::from(length).unwrap();
+            let mut chol = vec![T::zero(); n * n];
+            for i in 0..n {
+                for j in 0..=i {
+                    chol[i * n + j] = kernel(&us[i], &us[j], length);
+                }
+                chol[i * n + i] = chol[i * n + i] + <T as NumCast>::from(NUGGET).unwrap();
+            }
+            if linalg::cholesky(&mut chol, n).is_none() {
+                continue;
+            }
+
+            let mut alpha = ys.clone();
+            forward(&chol, &mut alpha);
+            backward(&chol, &mut alpha);
+
+            // Profile likelihood, the variance being the maximum likelihood one
+            let variance = ys
+                .iter()
+                .zip(&alpha)
+                .fold(T::zero(), |acc, (&y, &a)| acc + y * a)
+                / count;
+            let log_det = (0..n).fold(T::zero(), |acc, i| acc + chol[i * n + i].ln());
+            let likelihood = -(count * variance.max(T::min_positive_value()).ln())
+                / (T::one() + T::one())
+                - log_det;
+
+            if best.as_ref().is_none_or(|(best, _)| likelihood > *best) {
+                let process = Process {
+                    us: us.to_vec(),
+                    chol,
+                    alpha,
+                    length,
+                    variance,
+                    mean,
+                    scale,
+                };
+                best = Some((likelihood, process));
+            }
+        }
+
+        best.map(|(_, process)| process)
+    }
+
+    /// Gives the predicted mean and standard deviation at `u`.
+    fn predict(&self, u: &[T]) -> (T, T) {
+        let mut k: Vec<T> = self.us.iter().map(|v| kernel(u, v, self.length)).collect();
+        let mean = k
+            .iter()
+            .zip(&self.alpha)
+            .fold(T::zero(), |acc, (&ki, &ai)| ki.mul_add(ai, acc));
+
+        forward(&self.chol, &mut k);
+        let explained = k.iter().fold(T::zero(), |acc, &vi| vi.mul_add(vi, acc));
+        let std = ((T::one() - explained).max(T::zero()) * self.variance).sqrt();
+
+        (mean.mul_add(self.scale, self.mean), std * self.scale)
+    }
+}
+
+impl<A: Array> BayesianOptimization<A>
+where
+    A::Item: Float,
+{
+    /// Sets the initial sample count, per dimension.
+    pub fn init(mut self, init: usize) -> BayesianOptimization<A> {
+        self.init = init;
+        self
+    }
+
+    /// Sets the exploration margin, the improvement over the best value
+    /// sought relative to the spread of the values.
+    pub fn xi(mut self, xi: A::Item) -> BayesianOptimization<A> {
+        self.xi = xi;
+        self
+    }
+
+    /// Sets the iteration limit, one evaluation each.
+    pub fn max_iter(mut self, max_iter: MaxIter) -> BayesianOptimization<A> {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Sets the function evaluation limit, the initial samples included.
+    ///
+    /// The iterations end normally there.
+    pub fn max_fev(mut self, max_fev: usize) -> BayesianOptimization<A> {
+        self.max_fev = Some(max_fev);
+        self
+    }
+
+    /// Sets the seed of the random generator.
+    pub fn seed(mut self, seed: u64) -> BayesianOptimization<A> {
+        self.seed = seed;
+        self
+    }
+
+    /// Minimizes the function `f` within the box `bounds`,
+    /// given as `(lower, upper)` pairs.
+    pub fn minimize<F>(&self, bounds: &[(A::Item, A::Item)], f: F) -> Result<A>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        let cast = |x: f64| <A::Item as NumCast>::from(x).unwrap();
+        let dim = bounds.len();
+        let unit = vec![(A::Item::zero(), A::Item::one()); dim];
+        let scale = |u: &[A::Item]| -> Vector<A> {
+            bounds
+                .iter()
+                .zip(u)
+                .map(|(&(lower, upper), &ui)| lower + (upper - lower) * ui)
+                .collect()
+        };
+        let mut rng = Rng::new(self.seed);
+        let mut objective = Objective::new(f, false, 0);
+
+        // Init
+        let mut us: Vec<Vec<A::Item>> = Vec::new();
+        let mut fs = Vec::new();
+        let init = (self.init * dim).max(2);
+        let init = self
+            .max_fev
+            .map_or(init, |max_fev| init.min(max_fev.max(1)));
+        for _ in 0..init {
+            let u = rng
+                .sample_in::<A>(&unit)
+                .iter()
+                .copied()
+                .collect::<Vec<_>>();
+            fs.push(objective.call(&scale(&u), Move::Init));
+            us.push(u);
+        }
+
+        let max_iter = self.max_iter.limit(dim);
+        let max_iter = self.max_fev.map_or(max_iter, |max_fev| {
+            max_iter.min(max_fev.saturating_sub(init))
+        });
+        for iter in 0..max_iter {
+            objective.iter = iter;
+
+            let f_best = fs.iter().fold(A::Item::infinity(), |acc, &f| acc.min(f));
+            let next = Process::fit(&us, &fs).and_then(|process| {
+                let margin = self.xi * process.scale;
+                let acquisition = |u: &A| {
+                    let u = u.as_ref();
+                    if u.iter()
+                        .any(|&ui| ui < A::Item::zero() || A::Item::one() < ui)
+                    {
+                        return A::Item::zero();
+                    }
+                    let (mean, std) = process.predict(u);
+                    -expected_improvement(f_best - margin - mean, std)
+                };
+
+                let mut minimizer = Minimizer::default().max_iter(MaxIter::PerDimension(100));
+                minimizer.step = cast(0.1);
+                minimizer.tol_f = A::Item::epsilon().sqrt() * process.scale;
+
+                // From the best samples and a few random points
+                let mut order: Vec<usize> = (0..fs.len()).collect();
+                order.sort_unstable_by(|&i, &j| {
+                    fs[i].partial_cmp(&fs[j]).unwrap_or(Ordering::Equal)
+                });
+                let starts: Vec<Vec<A::Item>> = order
+                    .iter()
+                    .take(3)
+                    .map(|&idx| us[idx].clone())
+                    .chain((0..5).map(|_| rng.sample_in::<A>(&unit).iter().copied().collect()))
+                    .collect();
+
+                starts
+                    .iter()
+                    .filter_map(|start| minimizer.minimize(start, acquisition).ok())
+                    .filter(|output| output.f_min < A::Item::zero())
+                    .min_by(|a, b| a.f_min.partial_cmp(&b.f_min).unwrap_or(Ordering::Equal))
+                    .map(|output| output.x_min.as_ref().to_vec())
+            });
+
+            // Random sample when nothing improves or the point is known
+            let u = match next {
+                Some(u) if us.iter().all(|v| distance(&u, v) > cast(1e-8)) => u,
+                _ => rng.sample_in::<A>(&unit).iter().copied().collect(),
+            };
+            fs.push(objective.call(&scale(&u), Move::Trial));
+            us.push(u);
+        }
+
+        let mut samples: Vec<Pair<A>> = fs
+            .into_iter()
+            .zip(&us)
+            .map(|(f, u)| Pair::new(f, scale(u)))
+            .collect();
+        samples.sort_unstable_by(|a, b| a.f.partial_cmp(&b.f).unwrap_or(Ordering::Equal));
+        let spread = spread(&samples[..(dim + 1).min(samples.len())], dim);
+        let best = samples.swap_remove(0);
+
+        Ok(Output {
+            f_min: best.f,
+            x_min: best.x.0,
+            iter: max_iter,
+            fev: objective.fev,
+            best_ever: objective.best.unwrap(),
+            termination: Termination::Iterations,
+            spread: spread.0,
+            log: Vec::new(),
+            phase: Phase::BayesianOptimization,
+            timing: None,
+            fitted: None,
+            trajectory: Vec::new(),
+        })
+    }
+}
+
+/// Gives the squared exponential kernel.
+fn kernel<T: Float>(u: &[T], v: &[T], length: T) -> T {
+    let squares = u
+        .iter()
+        .zip(v)
+        .fold(T::zero(), |acc, (&ui, &vi)| acc + (ui - vi).powi(2));
+    (-squares / (length * length * (T::one() + T::one()))).exp()
+}
+
+/// Solves `l x = b` in place, `l` being lower triangular.
+fn forward<T: Float>(l: &[T], b: &mut [T]) {
+    let n = b.len();
+    for i in 0..n {
+        let sum = (0..i).fold(b[i], |acc, k| acc - l[i * n + k] * b[k]);
+        b[i] = sum / l[i * n + i];
+    }
+}
+
+/// Solves `lᵀ x = b` in place, `l` being lower triangular.
+fn backward<T: Float>(l: &[T], b: &mut [T]) {
+    let n = b.len();
+    for i in (0..n).rev() {
+        let sum = (i + 1..n).fold(b[i], |acc, k| acc - l[k * n + i] * b[k]);
+        b[i] = sum / l[i * n + i];
+    }
+}
+
+/// Gives the expected improvement of a normal value
+/// of standard deviation `std`, `gap` below the target on average.
+fn expected_improvement<T: Float>(gap: T, std: T) -> T {
+    if std <= T::zero() {
+        return gap.max(T::zero());
+    }
+
+    let z = gap / std;
+    let sqrt_2 = T::one().hypot(T::one());
+    let two_pi = <T as NumCast>::from(2.0 * std::f64::consts::PI).unwrap();
+    let pdf = (-z * z / (T::one() + T::one())).exp() / two_pi.sqrt();
+    let cdf = erfc(-z / sqrt_2) / (T::one() + T::one());
+    gap * cdf + std * pdf
+}
+
+/// Gives the complementary error function (Abramowitz, M. and Stegun, I. A., 7.1.26).
+fn erfc<T: Float>(x: T) -> T {
+    let cast = |x: f64| <T as NumCast>::from(x).unwrap();
+    let t = (T::one() + cast(0.3275911) * x.abs()).recip();
+    let poly = [
+        1.061405429,
+        -1.453152027,
+        1.421413741,
+        -0.284496736,
+        0.254829592,
+    ]
+    .iter()
+    .fold(T::zero(), |acc, &c| (acc + cast(c)) * t);
+    let tail = poly * (-x * x).exp();
+    if x < T::zero() {
+        cast(2.0) - tail
+    } else {
+        tail
+    }
+}
+
+/// Gives the largest coordinate difference.
+fn distance<T: Float>(u: &[T], v: &[T]) -> T {
+    u.iter()
+        .zip(v)
+        .fold(T::zero(), |acc, (&ui, &vi)| acc.max((ui - vi).abs()))
+}
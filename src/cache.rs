@@ -0,0 +1,134 @@
+use array::Array;
+use num_traits::Float;
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Function values memoized on the exact parameters,
+/// optionally persisted to an append-only file.
+///
+/// Each line of the file holds the coordinates of a point and its value,
+/// so that a calibration resumed after a restart reuses its evaluations.
+#[derive(Debug)]
+pub struct Cache<T> {
+    values: HashMap<Vec<u64>, T>,
+    file: Option<File>,
+    hits: usize,
+}
+
+impl<T> Default for Cache<T> {
+    fn default() -> Cache<T> {
+        Cache {
+            values: HashMap::new(),
+            file: None,
+            hits: 0,
+        }
+    }
+}
+
+impl<T: Float + Display + FromStr> Cache<T> {
+    /// Creates an in-memory cache.
+    pub fn new() -> Cache<T> {
+        Cache::default()
+    }
+
+    /// Opens the cache stored in the file `path`, created if needed,
+    /// to which the new evaluations are appended.
+    ///
+    /// A malformed line is skipped. A last line without its terminator,
+    /// cut by a crash, is dropped from the file.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Cache<T>> {
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+
+        let mut values = HashMap::new();
+        let mut reader = BufReader::new(&file);
+        let mut line = Vec::new();
+        let mut len = 0;
+        loop {
+            line.clear();
+            let read = reader.read_until(b'\n', &mut line)?;
+            if read == 0 || line.last() != Some(&b'\n') {
+                break;
+            }
+            len += read as u64;
+
+            let line = String::from_utf8_lossy(&line);
+            let parsed: Option<Vec<T>> = line.split_whitespace().map(|s| s.parse().ok()).collect();
+            if let Some((&f, x)) = parsed.as_deref().and_then(<[T]>::split_last) {
+                values.insert(key(x), f);
+            }
+        }
+        // The new entries must not extend a cut line
+        if len < file.metadata()?.len() {
+            file.set_len(len)?;
+        }
+
+        Ok(Cache {
+            values,
+            file: Some(file),
+            hits: 0,
+        })
+    }
+
+    /// Gives the number of points stored.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Tells whether no point is stored.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Gives the number of evaluations served from the cache.
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    /// Wraps the function `f`, evaluating it only at unknown points.
+    ///
+    /// The wrapper panics if the file cannot be written.
+    pub fn function<'a, A, F>(&'a mut self, mut f: F) -> impl FnMut(&A) -> T + 'a
+    where
+        A: Array<Item = T>,
+        F: FnMut(&A) -> T + 'a,
+    {
+        move |x: &A| {
+            let k = key(x.as_ref());
+            if let Some(&fx) = self.values.get(&k) {
+                self.hits += 1;
+                return fx;
+            }
+
+            let fx = f(x);
+            if let Some(file) = &mut self.file {
+                let mut line = String::new();
+                for xi in x.as_ref() {
+                    line += &format!("{} ", xi);
+                }
+                writeln!(file, "{}{}", line, fx).expect("the cache file cannot be written");
+            }
+            self.values.insert(k, fx);
+            fx
+        }
+    }
+}
+
+/// Gives the exact bit patterns of the coordinates.
+fn key<T: Float>(x: &[T]) -> Vec<u64> {
+    x.iter()
+        .map(|xi| xi.to_f64().map_or(u64::MAX, f64::to_bits))
+        .collect()
+}
@@ -0,0 +1,318 @@
+use array::Array;
+use num_traits::Float;
+use num_traits::NumCast;
+use num_traits::One;
+use num_traits::Zero;
+
+use crate::global::Phase;
+use crate::linalg;
+use crate::minimizer::tolerance;
+use crate::minimizer::Error;
+use crate::minimizer::MaxIter;
+use crate::minimizer::Output;
+use crate::minimizer::Result;
+use crate::minimizer::Termination;
+use crate::objective::BestSoFar;
+use crate::objective::Move;
+use crate::objective::Objective;
+use crate::rng::Rng;
+use crate::simplex::Pair;
+use crate::vector::Vector;
+
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// A structure that holds all the CMA-ES parameters.
+///
+/// The covariance matrix adaptation evolution strategy (Hansen, N., 2016),
+/// with a full covariance matrix.
+#[derive(Debug)]
+pub struct CmaEs<A: Array> {
+    // Initial step size parameter
+    sigma: A::Item,
+
+    // Population size parameter, derived from the dimension when unset
+    pop_size: Option<usize>,
+
+    // Tolerance (function) parameter
+    tol_f: A::Item,
+
+    // Tolerance (point) parameter
+    tol_x: A::Item,
+
+    // Generations parameter
+    max_iter: MaxIter,
+
+    // Function evaluations parameter
+    max_fev: Option<usize>,
+
+    // Random generator parameter
+    seed: u64,
+
+    // Shared best point parameter
+    best_so_far: Option<Arc<BestSoFar<A>>>,
+}
+
+impl<A: Array> Default for CmaEs<A>
+where
+    A::Item: Float,
+{
+    fn default() -> CmaEs<A> {
+        CmaEs {
+            sigma: <A::Item as NumCast>::from(0.3).unwrap(),
+            pop_size: None,
+            tol_f: tolerance(1e-10),
+            tol_x: tolerance(1e-10),
+            max_iter: MaxIter::PerDimension(200),
+            max_fev: None,
+            seed: 0,
+            best_so_far: None,
+        }
+    }
+}
+
+impl<A: Array> CmaEs<A>
+where
+    A::Item: Float,
+{
+    /// Sets the initial step size.
+    pub fn sigma(mut self, sigma: A::Item) -> CmaEs<A> {
+        self.sigma = sigma;
+        self
+    }
+
+    /// Sets the population size, `4 + 3 ln(n)` by default.
+    pub fn pop_size(mut self, pop_size: usize) -> CmaEs<A> {
+        self.pop_size = Some(pop_size);
+        self
+    }
+
+    /// Sets the generation limit.
+    pub fn max_iter(mut self, max_iter: MaxIter) -> CmaEs<A> {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Sets the function evaluation limit.
+    pub fn max_fev(mut self, max_fev: usize) -> CmaEs<A> {
+        self.max_fev = Some(max_fev);
+        self
+    }
+
+    /// Sets the seed of the random generator.
+    pub fn seed(mut self, seed: u64) -> CmaEs<A> {
+        self.seed = seed;
+        self
+    }
+
+    /// Publishes the best point to `handle` as the search goes,
+    /// for other threads to poll, see `Minimizer::best_so_far`.
+    pub fn best_so_far(mut self, handle: Arc<BestSoFar<A>>) -> CmaEs<A> {
+        self.best_so_far = Some(handle);
+        self
+    }
+
+    /// Minimizes the function `f` with the initial mean `x0`.
+    pub fn minimize<F>(&self, x0: &[A::Item], f: F) -> Result<A>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        let cast = |x: f64| <A::Item as NumCast>::from(x).unwrap();
+        let one = A::Item::one();
+        let two = cast(2.0);
+
+        let dim = x0.len();
+        let n = cast(dim as f64);
+        let mut rng = Rng::new(self.seed);
+        let mut objective = self.shared(Objective::new(f, false, 0));
+
+        // Selection
+        let lambda = self
+            .pop_size
+            .unwrap_or(4 + (3.0 * (dim as f64).ln()).floor() as usize)
+            .max(2);
+        let mu = lambda / 2;
+        let mut weights: Vec<A::Item> = (1..=mu)
+            .map(|i| cast((mu as f64 + 0.5).ln() - (i as f64).ln()))
+            .collect();
+        let sum = weights.iter().fold(A::Item::zero(), |acc, &w| acc + w);
+        weights.iter_mut().for_each(|w| *w = *w / sum);
+        let mu_eff = weights
+            .iter()
+            .fold(A::Item::zero(), |acc, &w| acc + w * w)
+            .recip();
+
+        // Adaptation
+        let cc = (cast(4.0) + mu_eff / n) / (n + cast(4.0) + two * mu_eff / n);
+        let cs = (mu_eff + two) / (n + mu_eff + cast(5.0));
+        let c1 = two / ((n + cast(1.3)).powi(2) + mu_eff);
+        let cmu =
+            (one - c1).min(two * (mu_eff - two + mu_eff.recip()) / ((n + two).powi(2) + mu_eff));
+        let damps = one + two * A::Item::zero().max(((mu_eff - one) / (n + one)).sqrt() - one) + cs;
+        let chi_n = n.sqrt() * (one - (cast(4.0) * n).recip() + (cast(21.0) * n * n).recip());
+
+        // State
+        let mut mean = Vector::<A>::from_slice(x0);
+        let mut sigma = self.sigma;
+        let mut pc = vec![A::Item::zero(); dim];
+        let mut ps = vec![A::Item::zero(); dim];
+        let mut cov = identity::<A::Item>(dim);
+        let mut basis = identity::<A::Item>(dim);
+        let mut scales = vec![one; dim];
+
+        let max_iter = self.max_iter.limit(dim);
+        for iter in 0..max_iter {
+            objective.iter = iter;
+
+            if let Some(max_fev) = self.max_fev {
+                if objective.fev >= max_fev {
+                    return Err(Error::MaxFev(max_fev));
+                }
+            }
+
+            // Sampling
+            let mut pop: Vec<Pair<A>> = (0..lambda)
+                .map(|_| {
+                    let z: Vec<A::Item> = (0..dim).map(|k| scales[k] * rng.normal()).collect();
+                    let x: Vector<A> = (0..dim)
+                        .map(|i| {
+                            let y = (0..dim).fold(A::Item::zero(), |acc, k| {
+                                basis[i * dim + k].mul_add(z[k], acc)
+                            });
+                            y.mul_add(sigma, mean[i])
+                        })
+                        .collect();
+                    Pair::new(objective.call(&x, Move::Trial), x)
+                })
+                .collect();
+            pop.sort_unstable_by(|a, b| a.f.partial_cmp(&b.f).unwrap_or(Ordering::Equal));
+
+            // Recombination
+            let old = mean;
+            mean = pop.iter().zip(&weights).fold(
+                Vector::zeros(dim),
+                |mut acc, (Pair { x, .. }, &w)| {
+                    acc.scaled_add(w, x);
+                    acc
+                },
+            );
+            let steps: Vec<Vec<A::Item>> = pop
+                .iter()
+                .take(mu)
+                .map(|Pair { x, .. }| (x - &old).iter().map(|&yi| yi / sigma).collect())
+                .collect();
+            let y_w: Vec<A::Item> = (&mean - &old).iter().map(|&yi| yi / sigma).collect();
+
+            // Step size path, with C^-1/2 = B D^-1 Bᵀ
+            let bt_y: Vec<A::Item> = (0..dim)
+                .map(|k| {
+                    (0..dim).fold(A::Item::zero(), |acc, i| {
+                        basis[i * dim + k].mul_add(y_w[i], acc)
+                    })
+                })
+                .collect();
+            let coef_s = (cs * (two - cs) * mu_eff).sqrt();
+            for (i, psi) in ps.iter_mut().enumerate() {
+                let white = (0..dim).fold(A::Item::zero(), |acc, k| {
+                    (basis[i * dim + k] * bt_y[k] / scales[k]) + acc
+                });
+                *psi = (one - cs).mul_add(*psi, coef_s * white);
+            }
+            let ps_norm = ps
+                .iter()
+                .fold(A::Item::zero(), |acc, &p| p.mul_add(p, acc))
+                .sqrt();
+
+            // Covariance path
+            let generations = cast(2.0 * (iter + 1) as f64);
+            let hsig = ps_norm / (one - (one - cs).powf(generations)).sqrt() / chi_n
+                < cast(1.4) + two / (n + one);
+            let coef_c = if hsig {
+                (cc * (two - cc) * mu_eff).sqrt()
+            } else {
+                A::Item::zero()
+            };
+            for (pci, &yi) in pc.iter_mut().zip(&y_w) {
+                *pci = (one - cc).mul_add(*pci, coef_c * yi);
+            }
+
+            // Covariance update
+            let correction = if hsig {
+                A::Item::zero()
+            } else {
+                c1 * cc * (two - cc)
+            };
+            for i in 0..dim {
+                for j in 0..=i {
+                    let rank_mu = steps
+                        .iter()
+                        .zip(&weights)
+                        .fold(A::Item::zero(), |acc, (y, &w)| {
+                            (w * y[i]).mul_add(y[j], acc)
+                        });
+                    let cij = (one - c1 - cmu + correction) * cov[i * dim + j]
+                        + c1 * pc[i] * pc[j]
+                        + cmu * rank_mu;
+                    cov[i * dim + j] = cij;
+                    cov[j * dim + i] = cij;
+                }
+            }
+
+            // Step size update
+            sigma = sigma * ((cs / damps) * (ps_norm / chi_n - one)).exp();
+
+            // Decomposition
+            let (values, vectors) = linalg::symmetric_eigen(&mut cov.clone(), dim);
+            basis = vectors;
+            scales = values
+                .into_iter()
+                .map(|value| value.max(A::Item::zero()).sqrt())
+                .collect();
+
+            // Termination tests
+            let test_f = pop[lambda - 1].f - pop[0].f;
+            let test_x = sigma * scales.iter().fold(A::Item::zero(), |acc, &d| acc.max(d));
+
+            if test_f <= self.tol_f && test_x <= self.tol_x {
+                let best = objective.best.clone().unwrap();
+                let spread: Vector<A> = (0..dim).map(|i| sigma * cov[i * dim + i].sqrt()).collect();
+
+                return Ok(Output {
+                    f_min: best.f,
+                    x_min: best.x.clone(),
+                    iter,
+                    fev: objective.fev,
+                    best_ever: best,
+                    termination: Termination::Tolerance,
+                    spread: spread.0,
+                    log: Vec::new(),
+                    phase: Phase::CmaEs,
+                    timing: None,
+                    fitted: None,
+                    trajectory: Vec::new(),
+                });
+            }
+        }
+
+        Err(Error::MaxIter {
+            limit: self.max_iter,
+            iter: max_iter,
+        })
+    }
+
+    /// Publishes the best point of `objective` to the shared handle, if any.
+    fn shared<F>(&self, objective: Objective<A, F>) -> Objective<A, F> {
+        match &self.best_so_far {
+            Some(handle) => objective.sharing(handle.clone()),
+            None => objective,
+        }
+    }
+}
+
+fn identity<T: Float>(dim: usize) -> Vec<T> {
+    let mut id = vec![T::zero(); dim * dim];
+    for idx in 0..dim {
+        id[idx * dim + idx] = T::one();
+    }
+    id
+}
@@ -0,0 +1,374 @@
+use array::Array;
+use num_traits::Float;
+use num_traits::NumCast;
+use num_traits::Zero;
+
+use crate::global::Phase;
+use crate::linalg;
+use crate::minimizer::tolerance;
+use crate::minimizer::Error;
+use crate::minimizer::MaxIter;
+use crate::minimizer::Output;
+use crate::minimizer::Point;
+use crate::minimizer::Result;
+use crate::minimizer::Termination;
+use crate::objective::Move;
+use crate::objective::Objective;
+use crate::vector::Vector;
+
+use std::cmp::Ordering;
+
+/// A structure that holds all the COBYLA parameters.
+///
+/// Constrained optimization by linear approximations (Powell, M. J. D., 1994),
+/// minimizing `f` subject to `c(x) >= 0` with linear models of both
+/// interpolating the simplex vertices, and a linear program solved
+/// in a box trust region at each step.
+#[derive(Debug)]
+pub struct Cobyla<A: Array> {
+    // Initial trust radius parameter
+    rho: A::Item,
+
+    // Tolerance (final trust radius) parameter
+    tol_x: A::Item,
+
+    // Iterations parameter
+    max_iter: MaxIter,
+
+    // Function evaluations parameter
+    max_fev: Option<usize>,
+}
+
+impl<A: Array> Default for Cobyla<A>
+where
+    A::Item: Float,
+{
+    fn default() -> Cobyla<A> {
+        Cobyla {
+            rho: <A::Item as NumCast>::from(0.5).unwrap(),
+            tol_x: tolerance(1e-6),
+            max_iter: MaxIter::PerDimension(500),
+            max_fev: None,
+        }
+    }
+}
+
+/// Gradients of the function and of the constraints.
+type Models<T> = (Vec<T>, Vec<Vec<T>>);
+
+/// Simplex vertex, with the constraint values.
+struct Vertex<A: Array> {
+    x: Vector<A>,
+    f: A::Item,
+    c: Vec<A::Item>,
+}
+
+impl<A: Array> Vertex<A>
+where
+    A::Item: Float,
+{
+    /// Gives the largest constraint violation.
+    fn violation(&self) -> A::Item {
+        self.c.iter().fold(A::Item::zero(), |acc, &ci| acc.max(-ci))
+    }
+
+    /// Gives the value penalized by the violation.
+    fn merit(&self, mu: A::Item) -> A::Item {
+        mu.mul_add(self.violation(), self.f)
+    }
+}
+
+impl<A: Array> Cobyla<A>
+where
+    A::Item: Float,
+{
+    /// Sets the initial trust radius.
+    pub fn rho(mut self, rho: A::Item) -> Cobyla<A> {
+        self.rho = rho;
+        self
+    }
+
+    /// Sets the final trust radius.
+    pub fn tol_x(mut self, tol_x: A::Item) -> Cobyla<A> {
+        self.tol_x = tol_x;
+        self
+    }
+
+    /// Sets the iteration limit.
+    pub fn max_iter(mut self, max_iter: MaxIter) -> Cobyla<A> {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Sets the function evaluation limit.
+    pub fn max_fev(mut self, max_fev: usize) -> Cobyla<A> {
+        self.max_fev = Some(max_fev);
+        self
+    }
+
+    /// Minimizes the function `f` with the seed `x0`,
+    /// subject to every value given by `constraints` being nonnegative.
+    ///
+    /// The best point weighs the function value against the constraint
+    /// violation, it may violate the constraints when no point is feasible.
+    pub fn minimize<F, G>(&self, x0: &[A::Item], f: F, mut constraints: G) -> Result<A>
+    where
+        F: FnMut(&A) -> A::Item,
+        G: FnMut(&A) -> Vec<A::Item>,
+    {
+        let cast = |x: f64| <A::Item as NumCast>::from(x).unwrap();
+        let two = cast(2.0);
+
+        let dim = x0.len();
+        let mut objective = Objective::new(f, false, 0);
+        let max_fev = self.max_fev;
+        let mut evaluate = |objective: &mut Objective<A, F>, x: Vector<A>, kind: Move| {
+            // The limit is checked before each evaluation
+            objective.budget(max_fev).map(|()| Vertex {
+                f: objective.call(&x, kind),
+                c: constraints(&x),
+                x,
+            })
+        };
+
+        // Init
+        let mut rho = self.rho;
+        let mut mu = A::Item::zero();
+        let start = Vector::<A>::from_slice(x0);
+        let mut vertices = vec![evaluate(&mut objective, start.clone(), Move::Init)?];
+        for idx in 0..dim {
+            let x = axial(&start, idx, rho);
+            vertices.push(evaluate(&mut objective, x, Move::Init)?);
+        }
+
+        let max_iter = self.max_iter.limit(dim);
+        for iter in 0..max_iter {
+            objective.iter = iter;
+
+            // Best vertex first
+            let best = (0..vertices.len())
+                .min_by(|&i, &j| compare(&vertices[i], &vertices[j], mu))
+                .unwrap();
+            vertices.swap(0, best);
+
+            // Linear models
+            let (g, grads) = match models(&vertices) {
+                Some(models) => models,
+                None => {
+                    let best = &vertices[0].x;
+                    let rebuilt: Vec<_> = (0..dim).map(|idx| axial(best, idx, rho)).collect();
+                    vertices.truncate(1);
+                    for x in rebuilt {
+                        vertices.push(evaluate(&mut objective, x, Move::Restart)?);
+                    }
+                    continue;
+                }
+            };
+
+            // Trust region step
+            let (d, violation) = step(&g, &grads, &vertices[0].c, rho);
+
+            // Penalty, keeping the predicted merit reduction positive
+            let decrease = vertices[0].violation() - violation;
+            let gd = g
+                .iter()
+                .zip(&d)
+                .fold(A::Item::zero(), |acc, (&gi, &di)| gi.mul_add(di, acc));
+            if decrease > A::Item::epsilon() * rho && gd > A::Item::zero() {
+                mu = mu.max(two * gd / decrease);
+            }
+
+            let (far, far_dist) = (1..vertices.len())
+                .map(|idx| (idx, distance(&vertices[idx].x, &vertices[0].x)))
+                .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+                .unwrap_or((0, A::Item::zero()));
+            let step_len = d.iter().fold(A::Item::zero(), |acc, di| acc.max(di.abs()));
+
+            let reduce = if step_len < rho / two {
+                // Short step, the geometry is improved first
+                if far_dist > two * rho {
+                    let edge = vertices[far].x.clone() - &vertices[0].x;
+                    let x = edge * (rho / far_dist) + &vertices[0].x;
+                    vertices[far] = evaluate(&mut objective, x, Move::Trial)?;
+                    false
+                } else {
+                    true
+                }
+            } else {
+                let x: Vector<A> = vertices[0]
+                    .x
+                    .iter()
+                    .zip(&d)
+                    .map(|(&xi, &di)| xi + di)
+                    .collect();
+                let trial = evaluate(&mut objective, x, Move::Trial)?;
+
+                if trial.merit(mu) < vertices[0].merit(mu) || far_dist > two * step_len {
+                    vertices[far] = trial;
+                    false
+                } else {
+                    true
+                }
+            };
+
+            if reduce {
+                if rho <= self.tol_x {
+                    let best = &vertices[0];
+                    let best = Point {
+                        f: best.f,
+                        x: best.x.0.clone(),
+                    };
+                    let spread: Vector<A> = (0..dim)
+                        .map(|idx| {
+                            let (min, max) = vertices.iter().fold(
+                                (A::Item::infinity(), A::Item::neg_infinity()),
+                                |(min, max), Vertex { x, .. }| (min.min(x[idx]), max.max(x[idx])),
+                            );
+                            max - min
+                        })
+                        .collect();
+
+                    return Ok(Output {
+                        f_min: best.f,
+                        x_min: best.x.clone(),
+                        iter,
+                        fev: objective.fev,
+                        best_ever: best,
+                        termination: Termination::Tolerance,
+                        spread: spread.0,
+                        log: Vec::new(),
+                        phase: Phase::Cobyla,
+                        timing: None,
+                        fitted: None,
+                        trajectory: Vec::new(),
+                    });
+                }
+                rho = (rho / two).max(self.tol_x);
+            }
+        }
+
+        Err(Error::MaxIter {
+            limit: self.max_iter,
+            iter: max_iter,
+        })
+    }
+}
+
+/// Orders the vertices by merit, then by violation.
+fn compare<A: Array>(a: &Vertex<A>, b: &Vertex<A>, mu: A::Item) -> Ordering
+where
+    A::Item: Float,
+{
+    a.merit(mu)
+        .partial_cmp(&b.merit(mu))
+        .unwrap_or(Ordering::Equal)
+        .then(
+            a.violation()
+                .partial_cmp(&b.violation())
+                .unwrap_or(Ordering::Equal),
+        )
+}
+
+/// Moves the coordinate `idx` of `x` by `rho`.
+fn axial<A: Array>(x: &Vector<A>, idx: usize, rho: A::Item) -> Vector<A>
+where
+    A::Item: Float,
+{
+    let mut x = x.clone();
+    x[idx] = x[idx] + rho;
+    x
+}
+
+/// Gives the largest coordinate difference.
+fn distance<A: Array>(x: &Vector<A>, y: &Vector<A>) -> A::Item
+where
+    A::Item: Float,
+{
+    x.iter()
+        .zip(y.iter())
+        .fold(A::Item::zero(), |acc, (&xi, &yi)| acc.max((xi - yi).abs()))
+}
+
+/// Gives the gradients of the linear interpolations of the function
+/// and of the constraints on the vertices, the best one being first.
+fn models<A: Array>(vertices: &[Vertex<A>]) -> Option<Models<A::Item>>
+where
+    A::Item: Float,
+{
+    let (best, rest) = vertices.split_first()?;
+    let mut edges = Vec::with_capacity(rest.len() * rest.len());
+    for Vertex { x, .. } in rest {
+        edges.extend((x - &best.x).iter().copied());
+    }
+
+    let mut g: Vec<_> = rest.iter().map(|vertex| vertex.f - best.f).collect();
+    linalg::solve(&mut edges.clone(), &mut g)?;
+
+    let grads = (0..best.c.len())
+        .map(|i| {
+            let mut a: Vec<_> = rest.iter().map(|vertex| vertex.c[i] - best.c[i]).collect();
+            linalg::solve(&mut edges.clone(), &mut a)?;
+            Some(a)
+        })
+        .collect::<Option<_>>()?;
+
+    Some((g, grads))
+}
+
+/// Gives the step minimizing the linear model of the function in the box
+/// of half-width `rho`, once the violation of the linearized constraints
+/// is as small as possible there, along with that violation.
+///
+/// The step `d` is shifted to `u = d + rho` to fit the nonnegative variables
+/// of the linear programs.
+fn step<T: Float>(g: &[T], grads: &[Vec<T>], c: &[T], rho: T) -> (Vec<T>, T) {
+    let n = g.len();
+    let two = T::one() + T::one();
+    let offsets: Vec<T> = grads
+        .iter()
+        .zip(c)
+        .map(|(a, &ci)| a.iter().fold(ci, |acc, &aj| acc - aj * rho))
+        .collect();
+
+    // Box rows, u <= 2 rho
+    let box_rows = |a: &mut Vec<T>, b: &mut Vec<T>, width: usize| {
+        for j in 0..n {
+            a.extend((0..width).map(|k| if k == j { T::one() } else { T::zero() }));
+            b.push(two * rho);
+        }
+    };
+
+    // Smallest violation t, with c + a d + t >= 0
+    let mut violation = T::zero();
+    let mut u = vec![rho; n];
+    if !grads.is_empty() {
+        let (mut a, mut b) = (Vec::new(), Vec::new());
+        box_rows(&mut a, &mut b, n + 1);
+        for (grad, &offset) in grads.iter().zip(&offsets) {
+            a.extend(grad.iter().map(|&aj| -aj));
+            a.push(-T::one());
+            b.push(offset);
+        }
+        let mut cost = vec![T::zero(); n + 1];
+        cost[n] = T::one();
+
+        if let Some(solution) = linalg::linprog(&cost, &a, &b) {
+            violation = solution[n];
+            u.copy_from_slice(&solution[..n]);
+        }
+    }
+
+    // Best function model, keeping that violation
+    let slack = violation + T::epsilon().sqrt() * rho;
+    let (mut a, mut b) = (Vec::new(), Vec::new());
+    box_rows(&mut a, &mut b, n);
+    for (grad, &offset) in grads.iter().zip(&offsets) {
+        a.extend(grad.iter().map(|&aj| -aj));
+        b.push(offset + slack);
+    }
+    if let Some(solution) = linalg::linprog(g, &a, &b) {
+        u = solution;
+    }
+
+    (u.into_iter().map(|uj| uj - rho).collect(), violation)
+}
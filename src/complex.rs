@@ -0,0 +1,55 @@
+use array::Array;
+use num_complex::Complex;
+use num_traits::Float;
+use num_traits::One;
+
+use crate::minimizer::Minimizer;
+use crate::minimizer::Result;
+use crate::objective::Parameters;
+
+/// Complex parameters, as their real and imaginary parts in turn.
+impl<T: Float> Parameters<T> for Vec<Complex<T>> {
+    fn to_scalars(&self) -> Vec<T> {
+        self.iter().flat_map(|z| [z.re, z.im]).collect()
+    }
+
+    fn from_scalars(x: &[T]) -> Vec<Complex<T>> {
+        x.chunks_exact(2)
+            .map(|parts| Complex::new(parts[0], parts[1]))
+            .collect()
+    }
+}
+
+impl<A: Array> Minimizer<A>
+where
+    A::Item: Float,
+{
+    /// Minimizes the function `f` of complex parameters with the seed `z0`,
+    /// `A` holding their real and imaginary parts in turn.
+    ///
+    /// Unless set per coordinate, the point tolerance bounds the magnitude
+    /// of the spread of each complex parameter. The output parameters are
+    /// given by `Parameters::from_scalars`.
+    pub fn minimize_complex<F>(&self, z0: &[Complex<A::Item>], mut f: F) -> Result<A>
+    where
+        F: FnMut(&[Complex<A::Item>]) -> A::Item,
+    {
+        let mut minimizer = self.clone();
+        if minimizer.tol_x_each.is_none() {
+            // Both parts within tol / √2, the magnitude within tol
+            let tol = self.tol_x / A::Item::one().hypot(A::Item::one());
+            minimizer.tol_x_each = Some(vec![tol; 2 * z0.len()]);
+        }
+
+        let mut buf = Vec::with_capacity(z0.len());
+        minimizer.minimize(&z0.to_vec().to_scalars(), |x: &A| {
+            buf.clear();
+            buf.extend(
+                x.as_ref()
+                    .chunks_exact(2)
+                    .map(|parts| Complex::new(parts[0], parts[1])),
+            );
+            f(&buf)
+        })
+    }
+}
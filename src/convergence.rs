@@ -0,0 +1,58 @@
+use array::Array;
+use num_traits::Float;
+
+use crate::minimizer::Minimizer;
+use crate::minimizer::Output;
+
+/// Domain convergence of one coordinate of the final simplex.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Convergence<T> {
+    /// Coordinate tested.
+    pub index: usize,
+
+    /// Spread of the coordinate across the final simplex.
+    pub spread: T,
+
+    /// Point tolerance of the coordinate.
+    pub tol: T,
+
+    /// Spread relative to the tolerance, at most one once met.
+    pub ratio: T,
+
+    /// Whether the spread met the tolerance.
+    pub converged: bool,
+}
+
+impl<A: Array> Minimizer<A>
+where
+    A::Item: Float,
+{
+    /// Tests each coordinate spread of `output` against the point tolerance,
+    /// per coordinate if set.
+    ///
+    /// Unlike the termination test, which compares the best and worst
+    /// vertices, the spread spans the whole simplex: a coordinate far from
+    /// its tolerance while the others met theirs is usually not identified
+    /// by the function.
+    pub fn convergence(&self, output: &Output<A>) -> Vec<Convergence<A::Item>> {
+        output
+            .spread
+            .as_ref()
+            .iter()
+            .enumerate()
+            .map(|(index, &spread)| {
+                let tol = match &self.tol_x_each {
+                    Some(tol_x) => tol_x[index],
+                    None => self.tol_x,
+                };
+                Convergence {
+                    index,
+                    spread,
+                    tol,
+                    ratio: spread / tol,
+                    converged: spread <= tol,
+                }
+            })
+            .collect()
+    }
+}
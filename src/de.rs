@@ -0,0 +1,318 @@
+use array::Array;
+use num_traits::Float;
+use num_traits::NumCast;
+use num_traits::Zero;
+
+use crate::global::refine;
+use crate::global::spread;
+use crate::global::Phase;
+use crate::minimizer::tolerance;
+use crate::minimizer::Error;
+use crate::minimizer::MaxIter;
+use crate::minimizer::Minimizer;
+use crate::minimizer::Output;
+use crate::minimizer::Result;
+use crate::minimizer::Termination;
+use crate::objective::BestSoFar;
+use crate::objective::Move;
+use crate::objective::Objective;
+use crate::rng::Rng;
+use crate::simplex::Pair;
+use crate::vector::Vector;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use std::sync::Arc;
+
+/// A structure that holds all the differential evolution parameters.
+///
+/// It searches the global minimum within box bounds
+/// with the rand/1/bin strategy.
+#[derive(Debug)]
+pub struct DifferentialEvolution<A: Array> {
+    // Population size parameter, per dimension
+    pop_size: usize,
+
+    // Differential weight parameter
+    weight: A::Item,
+
+    // Crossover probability parameter
+    crossover: A::Item,
+
+    // Tolerance (function) parameter
+    tol_f: A::Item,
+
+    // Tolerance (point) parameter
+    tol_x: A::Item,
+
+    // Generations parameter
+    max_iter: MaxIter,
+
+    // Function evaluations parameter
+    max_fev: Option<usize>,
+
+    // Random generator parameter
+    seed: u64,
+
+    // Local refinement parameter
+    pub(crate) local: Option<Minimizer<A>>,
+
+    // Shared best point parameter
+    best_so_far: Option<Arc<BestSoFar<A>>>,
+}
+
+impl<A: Array> Default for DifferentialEvolution<A>
+where
+    A::Item: Float,
+{
+    fn default() -> DifferentialEvolution<A> {
+        DifferentialEvolution {
+            pop_size: 15,
+            weight: <A::Item as NumCast>::from(0.8).unwrap(),
+            crossover: <A::Item as NumCast>::from(0.7).unwrap(),
+            tol_f: tolerance(1e-4),
+            tol_x: tolerance(1e-4),
+            max_iter: MaxIter::Absolute(1000),
+            max_fev: None,
+            seed: 0,
+            local: None,
+            best_so_far: None,
+        }
+    }
+}
+
+impl<A: Array> DifferentialEvolution<A>
+where
+    A::Item: Float,
+{
+    /// Sets the population size, per dimension.
+    pub fn pop_size(mut self, pop_size: usize) -> DifferentialEvolution<A> {
+        self.pop_size = pop_size;
+        self
+    }
+
+    /// Sets the differential weight, usually in `[0.5, 1]`.
+    pub fn weight(mut self, weight: A::Item) -> DifferentialEvolution<A> {
+        self.weight = weight;
+        self
+    }
+
+    /// Sets the crossover probability.
+    pub fn crossover(mut self, crossover: A::Item) -> DifferentialEvolution<A> {
+        self.crossover = crossover;
+        self
+    }
+
+    /// Sets the generation limit.
+    pub fn max_iter(mut self, max_iter: MaxIter) -> DifferentialEvolution<A> {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Sets the function evaluation limit, the local refinement included.
+    pub fn max_fev(mut self, max_fev: usize) -> DifferentialEvolution<A> {
+        self.max_fev = Some(max_fev);
+        self
+    }
+
+    /// Sets the seed of the random generator.
+    pub fn seed(mut self, seed: u64) -> DifferentialEvolution<A> {
+        self.seed = seed;
+        self
+    }
+
+    /// Refines the best member with the Nelder-Mead `minimizer`.
+    pub fn local(mut self, minimizer: Minimizer<A>) -> DifferentialEvolution<A> {
+        self.local = Some(minimizer);
+        self
+    }
+
+    /// Publishes the best point to `handle` as the search goes,
+    /// for other threads to poll, see `Minimizer::best_so_far`.
+    pub fn best_so_far(mut self, handle: Arc<BestSoFar<A>>) -> DifferentialEvolution<A> {
+        self.best_so_far = Some(handle);
+        self
+    }
+
+    /// Minimizes the function `f` within the box `bounds`,
+    /// given as `(lower, upper)` pairs.
+    pub fn minimize<F>(&self, bounds: &[(A::Item, A::Item)], mut f: F) -> Result<A>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        let mut objective = self.shared(Objective::new(&mut f, false, 0));
+        let output = self.run(bounds, &mut objective, |objective, xs, kind| {
+            xs.iter().map(|x| objective.call(x, kind)).collect()
+        })?;
+
+        Ok(refine(
+            self.local.as_ref(),
+            self.best_so_far.as_ref(),
+            self.max_fev,
+            output,
+            f,
+        ))
+    }
+
+    /// Minimizes the function `f` within the box `bounds`,
+    /// evaluating the trials of a generation in parallel.
+    ///
+    /// The trials are drawn beforehand, the result does not depend
+    /// on the thread count and matches the one of `minimize`.
+    #[cfg(feature = "rayon")]
+    pub fn minimize_par<F>(&self, bounds: &[(A::Item, A::Item)], f: F) -> Result<A>
+    where
+        A: Send + Sync,
+        A::Item: Send + Sync,
+        F: Fn(&A) -> A::Item + Sync,
+    {
+        let mut objective = self.shared(Objective::new((), false, 0));
+        let output = self.run(bounds, &mut objective, |objective, xs, kind| {
+            let fs: Vec<A::Item> = xs.par_iter().map(|x| f(x)).collect();
+            for (x, &fx) in xs.iter().zip(&fs) {
+                objective.record(x, fx, kind);
+            }
+            fs
+        })?;
+
+        Ok(refine(
+            self.local.as_ref(),
+            self.best_so_far.as_ref(),
+            self.max_fev,
+            output,
+            &f,
+        ))
+    }
+
+    /// Publishes the best point of `objective` to the shared handle, if any.
+    fn shared<F>(&self, objective: Objective<A, F>) -> Objective<A, F> {
+        match &self.best_so_far {
+            Some(handle) => objective.sharing(handle.clone()),
+            None => objective,
+        }
+    }
+
+    /// Runs the evolution, `evaluate` giving the values of a whole generation.
+    ///
+    /// The trials of a generation are all drawn from the previous one.
+    fn run<F, E>(
+        &self,
+        bounds: &[(A::Item, A::Item)],
+        objective: &mut Objective<A, F>,
+        mut evaluate: E,
+    ) -> Result<A>
+    where
+        E: FnMut(&mut Objective<A, F>, &[Vector<A>], Move) -> Vec<A::Item>,
+    {
+        let dim = bounds.len();
+        let len = (self.pop_size * dim).max(4);
+        let mut rng = Rng::new(self.seed);
+
+        // Init
+        let xs: Vec<Vector<A>> = (0..len).map(|_| rng.sample_in(bounds)).collect();
+        let fs = evaluate(objective, &xs, Move::Init);
+        let mut pop: Vec<Pair<A>> = fs
+            .into_iter()
+            .zip(xs)
+            .map(|(f, x)| Pair::new(f, x))
+            .collect();
+
+        let max_iter = self.max_iter.limit(dim);
+        for iter in 0..max_iter {
+            objective.iter = iter;
+
+            if let Some(max_fev) = self.max_fev {
+                if objective.fev >= max_fev {
+                    return Err(Error::MaxFev(max_fev));
+                }
+            }
+
+            // Mutation
+            let trials: Vec<Vector<A>> = (0..len)
+                .map(|idx| {
+                    let [r1, r2, r3] = distinct(&mut rng, len, idx);
+                    let j_rand = rng.below(dim);
+                    bounds
+                        .iter()
+                        .enumerate()
+                        .map(|(j, &(lower, upper))| {
+                            // Crossover
+                            if j != j_rand && rng.uniform::<A::Item>() >= self.crossover {
+                                return pop[idx].x[j];
+                            }
+
+                            let xj =
+                                (pop[r2].x[j] - pop[r3].x[j]).mul_add(self.weight, pop[r1].x[j]);
+                            if lower <= xj && xj <= upper {
+                                xj
+                            } else {
+                                lower + (upper - lower) * rng.uniform()
+                            }
+                        })
+                        .collect()
+                })
+                .collect();
+
+            // Selection
+            let fs = evaluate(objective, &trials, Move::Trial);
+            for ((f_trial, trial), member) in fs.into_iter().zip(trials).zip(&mut pop) {
+                if f_trial <= member.f {
+                    *member = Pair::new(f_trial, trial);
+                }
+            }
+
+            // Termination tests
+            let (f_lo, f_hi) = pop.iter().fold(
+                (A::Item::infinity(), A::Item::neg_infinity()),
+                |(lo, hi), Pair { f, .. }| (lo.min(*f), hi.max(*f)),
+            );
+            let spread = spread(&pop, dim);
+            let test_x = spread.iter().fold(A::Item::zero(), |acc, &x| acc.max(x));
+            let test_f = f_hi - f_lo;
+
+            if test_f <= self.tol_f && test_x <= self.tol_x {
+                let best = pop
+                    .iter()
+                    .min_by(|a, b| a.f.partial_cmp(&b.f).unwrap_or(std::cmp::Ordering::Equal))
+                    .unwrap()
+                    .clone();
+
+                return Ok(Output {
+                    f_min: best.f,
+                    x_min: best.x.0,
+                    iter,
+                    fev: objective.fev,
+                    best_ever: objective.best.clone().unwrap(),
+                    termination: Termination::Tolerance,
+                    spread: spread.0,
+                    log: Vec::new(),
+                    phase: Phase::DifferentialEvolution,
+                    timing: None,
+                    fitted: None,
+                    trajectory: Vec::new(),
+                });
+            }
+        }
+
+        Err(Error::MaxIter {
+            limit: self.max_iter,
+            iter: max_iter,
+        })
+    }
+}
+
+/// Picks three distinct indices in `0..len`, all different from `idx`.
+fn distinct(rng: &mut Rng, len: usize, idx: usize) -> [usize; 3] {
+    let mut picks = [idx; 3];
+    for k in 0..3 {
+        loop {
+            let pick = rng.below(len);
+            if pick != idx && !picks[..k].contains(&pick) {
+                picks[k] = pick;
+                break;
+            }
+        }
+    }
+    picks
+}
@@ -0,0 +1,269 @@
+use array::Array;
+use num_traits::Float;
+use num_traits::NumCast;
+
+use crate::global::refine;
+use crate::global::Phase;
+use crate::minimizer::tolerance;
+use crate::minimizer::Error;
+use crate::minimizer::MaxIter;
+use crate::minimizer::Minimizer;
+use crate::minimizer::Output;
+use crate::minimizer::Result;
+use crate::minimizer::Termination;
+use crate::objective::Move;
+use crate::objective::Objective;
+use crate::vector::Vector;
+
+use std::cmp::Ordering;
+
+/// A structure that holds all the DIRECT parameters.
+///
+/// The dividing rectangles algorithm (Jones, D. R. et al., 1993)
+/// searches the global minimum within box bounds, trisecting the
+/// potentially optimal boxes of the normalized search space.
+#[derive(Debug)]
+pub struct Direct<A: Array> {
+    // Minimal relative improvement parameter
+    eps: A::Item,
+
+    // Tolerance (best box side, normalized) parameter
+    tol_x: A::Item,
+
+    // Iterations parameter
+    max_iter: MaxIter,
+
+    // Function evaluations parameter
+    max_fev: Option<usize>,
+
+    // Local refinement parameter
+    pub(crate) local: Option<Minimizer<A>>,
+}
+
+impl<A: Array> Default for Direct<A>
+where
+    A::Item: Float,
+{
+    fn default() -> Direct<A> {
+        Direct {
+            eps: tolerance(1e-4),
+            tol_x: tolerance(1e-4),
+            max_iter: MaxIter::Absolute(1000),
+            max_fev: None,
+            local: None,
+        }
+    }
+}
+
+/// Box of the normalized search space.
+struct Rect<T> {
+    center: Vec<T>,
+    // Number of trisections along each coordinate
+    levels: Vec<i32>,
+    f: T,
+}
+
+impl<T: Float> Rect<T> {
+    /// Gives the half diagonal.
+    fn size(&self) -> T {
+        let third = <T as NumCast>::from(3.0).unwrap().recip();
+        let squares = self
+            .levels
+            .iter()
+            .fold(T::zero(), |acc, &level| acc + third.powi(2 * level));
+        squares.sqrt() / (T::one() + T::one())
+    }
+}
+
+impl<A: Array> Direct<A>
+where
+    A::Item: Float,
+{
+    /// Sets the minimal relative improvement a box must promise to be divided.
+    pub fn eps(mut self, eps: A::Item) -> Direct<A> {
+        self.eps = eps;
+        self
+    }
+
+    /// Sets the side of the best box, relative to the bounds,
+    /// below which the search stops.
+    pub fn tol_x(mut self, tol_x: A::Item) -> Direct<A> {
+        self.tol_x = tol_x;
+        self
+    }
+
+    /// Sets the iteration limit.
+    pub fn max_iter(mut self, max_iter: MaxIter) -> Direct<A> {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Sets the function evaluation limit, the local refinement included.
+    pub fn max_fev(mut self, max_fev: usize) -> Direct<A> {
+        self.max_fev = Some(max_fev);
+        self
+    }
+
+    /// Refines the best box center with the Nelder-Mead `minimizer`.
+    pub fn local(mut self, minimizer: Minimizer<A>) -> Direct<A> {
+        self.local = Some(minimizer);
+        self
+    }
+
+    /// Minimizes the function `f` within the box `bounds`,
+    /// given as `(lower, upper)` pairs.
+    pub fn minimize<F>(&self, bounds: &[(A::Item, A::Item)], mut f: F) -> Result<A>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        let dim = bounds.len();
+        let third = <A::Item as NumCast>::from(3.0).unwrap().recip();
+        let scale = |center: &[A::Item]| -> Vector<A> {
+            bounds
+                .iter()
+                .zip(center)
+                .map(|(&(lower, upper), &ci)| lower + (upper - lower) * ci)
+                .collect()
+        };
+
+        // Init
+        let mut objective = Objective::new(&mut f, false, 0);
+        let center = vec![<A::Item as NumCast>::from(0.5).unwrap(); dim];
+        objective.budget(self.max_fev)?;
+        let mut rects = vec![Rect {
+            f: objective.call(&scale(&center), Move::Init),
+            center,
+            levels: vec![0; dim],
+        }];
+
+        let max_iter = self.max_iter.limit(dim);
+        for iter in 0..max_iter {
+            objective.iter = iter;
+
+            // Termination test
+            let best = (0..rects.len())
+                .min_by(|&i, &j| {
+                    rects[i]
+                        .f
+                        .partial_cmp(&rects[j].f)
+                        .unwrap_or(Ordering::Equal)
+                })
+                .unwrap();
+            let level = rects[best].levels.iter().copied().min().unwrap_or(0);
+            if third.powi(level) <= self.tol_x {
+                let best = &rects[best];
+                let spread: Vector<A> = bounds
+                    .iter()
+                    .zip(&best.levels)
+                    .map(|(&(lower, upper), &level)| (upper - lower) * third.powi(level))
+                    .collect();
+
+                let output = Output {
+                    f_min: best.f,
+                    x_min: scale(&best.center).0,
+                    iter,
+                    fev: objective.fev,
+                    best_ever: objective.best.unwrap(),
+                    termination: Termination::Tolerance,
+                    spread: spread.0,
+                    log: Vec::new(),
+                    phase: Phase::Direct,
+                    timing: None,
+                    fitted: None,
+                    trajectory: Vec::new(),
+                };
+
+                return Ok(refine(self.local.as_ref(), None, self.max_fev, output, f));
+            }
+
+            // Division of the potentially optimal boxes
+            for idx in potentially_optimal(&rects, self.eps) {
+                let level = rects[idx].levels.iter().copied().min().unwrap_or(0);
+                let delta = third.powi(level + 1);
+
+                // Sampling along the longest sides, within the limit
+                let mut samples = Vec::new();
+                for i in (0..dim).filter(|&i| rects[idx].levels[i] == level) {
+                    let mut children = Vec::with_capacity(2);
+                    for shift in [-delta, delta] {
+                        objective.budget(self.max_fev)?;
+                        let mut center = rects[idx].center.clone();
+                        center[i] = center[i] + shift;
+                        let f = objective.call(&scale(&center), Move::Trial);
+                        children.push((center, f));
+                    }
+                    samples.push((i, children));
+                }
+                samples.sort_by(|(_, a), (_, b)| {
+                    let wa = a[0].1.min(a[1].1);
+                    let wb = b[0].1.min(b[1].1);
+                    wa.partial_cmp(&wb).unwrap_or(Ordering::Equal)
+                });
+
+                // Trisection, the best coordinates first
+                for (i, children) in samples {
+                    rects[idx].levels[i] += 1;
+                    let levels = rects[idx].levels.clone();
+                    for (center, f) in children {
+                        rects.push(Rect {
+                            center,
+                            levels: levels.clone(),
+                            f,
+                        });
+                    }
+                }
+            }
+        }
+
+        Err(Error::MaxIter {
+            limit: self.max_iter,
+            iter: max_iter,
+        })
+    }
+}
+
+/// Gives the boxes on the lower right convex hull of the (size, value)
+/// diagram, promising an improvement by `eps` relative to the best value.
+fn potentially_optimal<T: Float>(rects: &[Rect<T>], eps: T) -> Vec<usize> {
+    // Best box of each size, by increasing size
+    let mut points: Vec<(T, T, usize)> = rects
+        .iter()
+        .enumerate()
+        .map(|(idx, rect)| (rect.size(), rect.f, idx))
+        .collect();
+    points.sort_by(|a, b| {
+        a.0.partial_cmp(&b.0)
+            .unwrap_or(Ordering::Equal)
+            .then(a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+    });
+    let tol = T::epsilon().sqrt();
+    points.dedup_by(|b, a| (b.0 - a.0).abs() <= tol * a.0);
+
+    // From the best one
+    let f_min = points.iter().fold(T::infinity(), |acc, p| acc.min(p.1));
+    let start = points.iter().rposition(|p| p.1 <= f_min).unwrap_or(0);
+
+    let mut hull: Vec<(T, T, usize)> = Vec::new();
+    for &p in &points[start..] {
+        while let [.., o, a] = hull[..] {
+            let cross = (a.0 - o.0) * (p.1 - o.1) - (a.1 - o.1) * (p.0 - o.0);
+            if cross > T::zero() {
+                break;
+            }
+            hull.pop();
+        }
+        hull.push(p);
+    }
+
+    let target = f_min - eps * f_min.abs();
+    (0..hull.len())
+        .filter(|&k| match hull.get(k + 1) {
+            Some(next) => {
+                let slope = (next.1 - hull[k].1) / (next.0 - hull[k].0);
+                hull[k].1 - slope * hull[k].0 <= target
+            }
+            None => true,
+        })
+        .map(|k| hull[k].2)
+        .collect()
+}
@@ -0,0 +1,205 @@
+use array::Array;
+use num_traits::Float;
+
+use crate::minimizer::Minimizer;
+use crate::minimizer::Result;
+use crate::multistart::MultiStart;
+use crate::objective::Function;
+use crate::vector::Vector;
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::panic;
+use std::pin::pin;
+use std::slice;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Wake;
+use std::task::Waker;
+use std::thread;
+
+/// Evaluates the function on batches of points, for instance
+/// on a thread pool, through a job queue or on remote workers.
+///
+/// Closures taking the points and giving their values are evaluators.
+pub trait Evaluator<A: Array> {
+    /// Gives the values of the points `xs`, in the same order.
+    fn evaluate(&mut self, xs: &[A]) -> Vec<A::Item>;
+}
+
+impl<A: Array, F: FnMut(&[A]) -> Vec<A::Item>> Evaluator<A> for F {
+    fn evaluate(&mut self, xs: &[A]) -> Vec<A::Item> {
+        self(xs)
+    }
+}
+
+/// Evaluates the function on batches of points asynchronously,
+/// the minimization waiting for each batch in turn.
+pub trait AsyncEvaluator<A: Array> {
+    /// Gives the values of the points `xs`, in the same order.
+    fn evaluate(&mut self, xs: &[A]) -> impl Future<Output = Vec<A::Item>>;
+}
+
+/// Evaluator spreading each batch over scoped threads.
+///
+/// A panic of the function is resumed in the minimization thread.
+pub struct Threads<F> {
+    // Function parameter
+    f: F,
+
+    // Thread count parameter
+    threads: usize,
+}
+
+impl<F> Debug for Threads<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Threads")
+            .field("threads", &self.threads)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<F> Threads<F> {
+    /// Evaluates the function `f` on as many threads as available.
+    pub fn new(f: F) -> Threads<F> {
+        Threads {
+            f,
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        }
+    }
+
+    /// Sets the thread count.
+    pub fn threads(mut self, threads: usize) -> Threads<F> {
+        assert!(threads > 0, "thread count must be positive");
+        self.threads = threads;
+        self
+    }
+}
+
+impl<A, F> Evaluator<A> for Threads<F>
+where
+    A: Array + Sync,
+    A::Item: Send,
+    F: Fn(&A) -> A::Item + Sync,
+{
+    fn evaluate(&mut self, xs: &[A]) -> Vec<A::Item> {
+        let chunk = xs.len().div_ceil(self.threads).max(1);
+        let f = &self.f;
+
+        thread::scope(|scope| {
+            let handles: Vec<_> = xs
+                .chunks(chunk)
+                .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|err| panic::resume_unwind(err))
+                })
+                .collect()
+        })
+    }
+}
+
+impl<A: Array> Minimizer<A>
+where
+    A::Item: Float,
+{
+    /// Minimizes the function evaluated by `evaluator` with the seed `x0`.
+    ///
+    /// The vertices of the initial simplex, of the shrinks and of the
+    /// restarts are evaluated as one batch each, as well as the candidates
+    /// of each iteration once speculative. Other points, such as the
+    /// reflections otherwise, are batches of one.
+    pub fn minimize_with<E>(&self, x0: &[A::Item], evaluator: E) -> Result<A>
+    where
+        E: Evaluator<A>,
+    {
+        self.start(Vector::from_slice(x0).0, Batched(evaluator))
+    }
+
+    /// Minimizes the function evaluated by `evaluator` asynchronously,
+    /// see `Minimizer::minimize_with`.
+    ///
+    /// The calling thread blocks on each batch.
+    pub fn minimize_async<E>(&self, x0: &[A::Item], evaluator: E) -> Result<A>
+    where
+        E: AsyncEvaluator<A>,
+    {
+        self.start(Vector::from_slice(x0).0, Blocking(evaluator))
+    }
+
+    /// Minimizes the function evaluated by `evaluator`
+    /// from each seed of `seeds` in turn,
+    /// see `Minimizer::minimize_multistart` and `Minimizer::minimize_with`.
+    pub fn minimize_multistart_with<V, E>(&self, seeds: &[V], evaluator: E) -> MultiStart<A>
+    where
+        V: AsRef<[A::Item]>,
+        E: Evaluator<A>,
+    {
+        self.multistart(seeds, Batched(evaluator))
+    }
+}
+
+/// Function evaluated batch by batch.
+struct Batched<E>(E);
+
+impl<A: Array, E: Evaluator<A>> Function<A, A::Item> for Batched<E> {
+    const BATCH: bool = true;
+
+    fn value(&mut self, x: &A) -> A::Item {
+        single(self.values(slice::from_ref(x)))
+    }
+
+    fn values(&mut self, xs: &[A]) -> Vec<A::Item> {
+        self.0.evaluate(xs)
+    }
+}
+
+/// Function evaluated batch by batch, waiting for each one.
+struct Blocking<E>(E);
+
+impl<A: Array, E: AsyncEvaluator<A>> Function<A, A::Item> for Blocking<E> {
+    const BATCH: bool = true;
+
+    fn value(&mut self, x: &A) -> A::Item {
+        single(self.values(slice::from_ref(x)))
+    }
+
+    fn values(&mut self, xs: &[A]) -> Vec<A::Item> {
+        block_on(self.0.evaluate(xs))
+    }
+}
+
+/// Gives the value of a batch of one point.
+fn single<T>(mut fs: Vec<T>) -> T {
+    assert_eq!(fs.len(), 1, "one value per point is expected");
+    fs.pop().unwrap()
+}
+
+/// Wakes the thread waiting for a future.
+struct Unpark(thread::Thread);
+
+impl Wake for Unpark {
+    fn wake(self: Arc<Unpark>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs `future` to completion on the current thread.
+fn block_on<T>(future: impl Future<Output = T>) -> T {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(value) => return value,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use array::Array;
+use num_traits::Float;
+
+use std::ffi::OsString;
+use std::fmt::Display;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::Child;
+use std::process::Command;
+use std::process::ExitStatus;
+use std::process::Stdio;
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Polling period of a running command with a timeout.
+const POLL: Duration = Duration::from_millis(5);
+
+/// How the parameters reach the command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExternalInput {
+    /// On one line of the standard input, separated by spaces.
+    Stdin,
+    /// As trailing arguments.
+    Args,
+    /// In a file, one per line, written before each run.
+    File(PathBuf),
+}
+
+/// External evaluation failure.
+#[derive(Debug)]
+pub enum ExternalError {
+    /// The command could not run.
+    Io(io::Error),
+    /// The command ran longer than the timeout, and was killed.
+    Timeout(Duration),
+    /// The command failed, with its standard error.
+    Status { status: ExitStatus, stderr: String },
+    /// The last line of the standard output is not a value.
+    Parse(String),
+}
+
+impl std::fmt::Display for ExternalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExternalError::Io(err) => write!(f, "Command failed to run: {}", err),
+            ExternalError::Timeout(timeout) => write!(f, "Command timed out ({:?})", timeout),
+            ExternalError::Status { status, stderr } => {
+                write!(f, "Command failed ({}): {}", status, stderr.trim())
+            }
+            ExternalError::Parse(line) => write!(f, "Command output is not a value: {:?}", line),
+        }
+    }
+}
+
+impl std::error::Error for ExternalError {}
+
+impl From<io::Error> for ExternalError {
+    fn from(err: io::Error) -> ExternalError {
+        ExternalError::Io(err)
+    }
+}
+
+/// Function evaluated by an external command, run once per point,
+/// its value being the last line of the standard output.
+///
+/// The function given by `External::function` panics on failures,
+/// which `Minimizer::catch_panics` turns into an error
+/// or an infeasible point.
+#[derive(Debug, Clone)]
+pub struct External {
+    // Program parameter
+    program: OsString,
+
+    // Leading arguments parameter
+    args: Vec<OsString>,
+
+    // Parameter passing parameter
+    input: ExternalInput,
+
+    // Time limit parameter
+    timeout: Option<Duration>,
+}
+
+impl External {
+    /// Evaluates the function by running `program`,
+    /// the parameters being written to its standard input.
+    pub fn new<S: Into<OsString>>(program: S) -> External {
+        External {
+            program: program.into(),
+            args: Vec::new(),
+            input: ExternalInput::Stdin,
+            timeout: None,
+        }
+    }
+
+    /// Adds an argument, before the parameters if they are passed as arguments.
+    pub fn arg<S: Into<OsString>>(mut self, arg: S) -> External {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Sets how the parameters reach the command.
+    pub fn input(mut self, input: ExternalInput) -> External {
+        self.input = input;
+        self
+    }
+
+    /// Kills the command once it runs longer than `timeout`.
+    pub fn timeout(mut self, timeout: Duration) -> External {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Runs the command at `x`, giving the value it prints last.
+    pub fn evaluate<T>(&self, x: &[T]) -> Result<T, ExternalError>
+    where
+        T: Display + FromStr,
+    {
+        let values: Vec<String> = x.iter().map(ToString::to_string).collect();
+
+        let mut command = Command::new(&self.program);
+        command
+            .args(&self.args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        match &self.input {
+            ExternalInput::Stdin => {
+                command.stdin(Stdio::piped());
+            }
+            ExternalInput::Args => {
+                command.args(&values).stdin(Stdio::null());
+            }
+            ExternalInput::File(path) => {
+                fs::write(path, values.join("\n") + "\n")?;
+                command.stdin(Stdio::null());
+            }
+        }
+
+        let mut child = command.spawn()?;
+
+        // Pipes fed and drained aside, so that neither a command
+        // ignoring its input nor a verbose one can block past the timeout
+        let stdin = feed(child.stdin.take(), values.join(" ") + "\n");
+        let stdout = drain(child.stdout.take());
+        let stderr = drain(child.stderr.take());
+        let status = self.wait(&mut child)?;
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+
+        // A command may exit without reading its input
+        match stdin.join().unwrap_or(Ok(())) {
+            Err(err) if err.kind() != io::ErrorKind::BrokenPipe => return Err(err.into()),
+            _ => (),
+        }
+
+        if !status.success() {
+            return Err(ExternalError::Status { status, stderr });
+        }
+
+        let line = stdout
+            .lines()
+            .rev()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default();
+        line.parse()
+            .map_err(|_| ExternalError::Parse(line.to_string()))
+    }
+
+    /// Gives the function to minimize, panicking on failures.
+    pub fn function<A>(&self) -> impl FnMut(&A) -> A::Item + '_
+    where
+        A: Array,
+        A::Item: Float + Display + FromStr,
+    {
+        move |x: &A| match self.evaluate(x.as_ref()) {
+            Ok(fx) => fx,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Waits for the command, killing it after the timeout.
+    fn wait(&self, child: &mut Child) -> Result<ExitStatus, ExternalError> {
+        let timeout = match self.timeout {
+            Some(timeout) => timeout,
+            None => return Ok(child.wait()?),
+        };
+
+        let started = Instant::now();
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(status);
+            }
+            if started.elapsed() >= timeout {
+                child.kill()?;
+                child.wait()?;
+                return Err(ExternalError::Timeout(timeout));
+            }
+            thread::sleep(POLL);
+        }
+    }
+}
+
+/// Writes `input` to `pipe` in another thread, closing it afterwards.
+fn feed<W: Write + Send + 'static>(
+    pipe: Option<W>,
+    input: String,
+) -> thread::JoinHandle<io::Result<()>> {
+    thread::spawn(move || match pipe {
+        Some(mut pipe) => pipe.write_all(input.as_bytes()),
+        None => Ok(()),
+    })
+}
+
+/// Reads `pipe` to the end in another thread.
+fn drain<R: Read + Send + 'static>(pipe: Option<R>) -> thread::JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut buf);
+        }
+        buf
+    })
+}
@@ -0,0 +1,147 @@
+use array::Array;
+use num_traits::Float;
+
+use crate::minimizer::Error;
+use crate::minimizer::Minimizer;
+use crate::minimizer::Output;
+use crate::minimizer::Point;
+use crate::objective::BestSoFar;
+use crate::simplex::Pair;
+use crate::vector::Vector;
+
+use std::sync::Arc;
+
+/// Phase that produced the estimation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    NelderMead,
+    Polish,
+    DifferentialEvolution,
+    CmaEs,
+    ParticleSwarm,
+    Cobyla,
+    Direct,
+    Spsa,
+    RandomSearch,
+    BayesianOptimization,
+}
+
+impl std::fmt::Display for Phase {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::NelderMead => write!(f, "Nelder-Mead"),
+            Phase::Polish => write!(f, "Quasi-Newton polish"),
+            Phase::DifferentialEvolution => write!(f, "Differential evolution"),
+            Phase::CmaEs => write!(f, "CMA-ES"),
+            Phase::ParticleSwarm => write!(f, "Particle swarm"),
+            Phase::Cobyla => write!(f, "COBYLA"),
+            Phase::Direct => write!(f, "DIRECT"),
+            Phase::Spsa => write!(f, "SPSA"),
+            Phase::RandomSearch => write!(f, "Random search"),
+            Phase::BayesianOptimization => write!(f, "Bayesian optimization"),
+        }
+    }
+}
+
+/// Refines the output with the local minimizer, if any,
+/// publishing its evaluations to `handle`.
+///
+/// The refinement stops at the evaluation limit `max_fev` of the search,
+/// if any, the evaluations of the search included. The output counts
+/// the evaluations of the refinement, even if it failed or did not
+/// improve on the estimation.
+pub(crate) fn refine<A: Array, F>(
+    local: Option<&Minimizer<A>>,
+    handle: Option<&Arc<BestSoFar<A>>>,
+    max_fev: Option<usize>,
+    mut output: Output<A>,
+    mut f: F,
+) -> Output<A>
+where
+    A::Item: Float,
+    F: FnMut(&A) -> A::Item,
+{
+    let mut minimizer = match local {
+        Some(minimizer) => minimizer.clone(),
+        None => return output,
+    };
+    if let Some(handle) = handle {
+        minimizer = minimizer.best_so_far(handle.clone());
+    }
+
+    // Remaining budget, the own limit of the minimizer still applying
+    let spent = match max_fev {
+        Some(max_fev) => {
+            let limit = minimizer
+                .max_fev
+                .map_or(max_fev, |own| own.saturating_add(output.fev).min(max_fev));
+            if limit <= output.fev {
+                return output;
+            }
+            minimizer.max_fev = Some(limit);
+            output.fev
+        }
+        None => 0,
+    };
+
+    // Best point of the refinement, kept even if it fails
+    let mut fev = 0;
+    let mut best: Option<Point<A>> = None;
+    let result = minimizer.after(spent, 0, |minimizer| {
+        minimizer.minimize(output.x_min.as_ref(), |x: &A| {
+            fev += 1;
+            let fx = f(x);
+            if best.as_ref().map_or(!fx.is_nan(), |best| fx < best.f) {
+                best = Some(Point {
+                    f: fx,
+                    x: x.clone(),
+                });
+            }
+            fx
+        })
+    });
+    let mut output = match result {
+        Ok(mut refined) if refined.f_min <= output.f_min => {
+            refined.iter += output.iter;
+            refined.fev = output.fev + fev;
+            if output.best_ever.f < refined.best_ever.f {
+                refined.best_ever = output.best_ever;
+            }
+            refined
+        }
+        Ok(refined) => {
+            output.iter += refined.iter;
+            output.fev += fev;
+            output
+        }
+        Err(err) => {
+            if let Error::MaxIter { iter, .. } = err {
+                output.iter += iter;
+            }
+            output.fev += fev;
+            output
+        }
+    };
+    if let Some(best) = best {
+        if best.f < output.best_ever.f {
+            output.best_ever = best;
+        }
+    }
+    output
+}
+
+/// Gives the range of each coordinate over the population.
+pub(crate) fn spread<A: Array>(pop: &[Pair<A>], dim: usize) -> Vector<A>
+where
+    A::Item: Float,
+{
+    (0..dim)
+        .map(|j| {
+            let (lo, hi) = pop.iter().fold(
+                (A::Item::infinity(), A::Item::neg_infinity()),
+                |(lo, hi), Pair { x, .. }| (lo.min(x[j]), hi.max(x[j])),
+            );
+            hi - lo
+        })
+        .collect()
+}
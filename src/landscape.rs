@@ -0,0 +1,160 @@
+use array::Array;
+use num_traits::Float;
+use num_traits::NumCast;
+
+use crate::minimizer::Output;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Function values along one coordinate through the estimation.
+#[derive(Debug, Clone)]
+pub struct Slice<T> {
+    /// Coordinate varied.
+    pub index: usize,
+
+    /// Coordinate values, evenly spaced.
+    pub xs: Vec<T>,
+
+    /// Function values.
+    pub fs: Vec<T>,
+}
+
+/// Function values on a grid of two coordinates through the estimation.
+#[derive(Debug, Clone)]
+pub struct Slice2<T> {
+    /// Coordinates varied.
+    pub indices: (usize, usize),
+
+    /// Values of the first coordinate, evenly spaced.
+    pub xs: Vec<T>,
+
+    /// Values of the second coordinate, evenly spaced.
+    pub ys: Vec<T>,
+
+    /// Function values, row-major: `fs[i * ys.len() + j]` is at `(xs[i], ys[j])`.
+    pub fs: Vec<T>,
+}
+
+impl<A: Array> Output<A>
+where
+    A::Item: Float,
+{
+    /// Evaluates the function `f` at `len` points along the coordinate `index`,
+    /// within `radius` of the estimation, the others being kept.
+    pub fn slice<F>(&self, index: usize, radius: A::Item, len: usize, mut f: F) -> Slice<A::Item>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        let xs = self.grid(index, radius, len);
+        let fs = xs.iter().map(|&x| f(&self.moved(&[(index, x)]))).collect();
+
+        Slice { index, xs, fs }
+    }
+
+    /// Evaluates the function `f` on a grid of `len × len` points
+    /// of the coordinates `indices`, within `radius` of the estimation
+    /// along each of them, the others being kept.
+    pub fn slice_2d<F>(
+        &self,
+        indices: (usize, usize),
+        radius: (A::Item, A::Item),
+        len: usize,
+        mut f: F,
+    ) -> Slice2<A::Item>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        let (xs, ys) = (
+            self.grid(indices.0, radius.0, len),
+            self.grid(indices.1, radius.1, len),
+        );
+        let fs = self.plane(indices, &xs, &ys).iter().map(&mut f).collect();
+
+        Slice2 {
+            indices,
+            xs,
+            ys,
+            fs,
+        }
+    }
+
+    /// Evaluates the function `f` along the coordinate `index`
+    /// in parallel, see `Output::slice`.
+    #[cfg(feature = "rayon")]
+    pub fn slice_par<F>(&self, index: usize, radius: A::Item, len: usize, f: F) -> Slice<A::Item>
+    where
+        A: Send + Sync,
+        A::Item: Send + Sync,
+        F: Fn(&A) -> A::Item + Sync,
+    {
+        let xs = self.grid(index, radius, len);
+        let points: Vec<A> = xs.iter().map(|&x| self.moved(&[(index, x)])).collect();
+        let fs = points.par_iter().map(&f).collect();
+
+        Slice { index, xs, fs }
+    }
+
+    /// Evaluates the function `f` on a grid of the coordinates `indices`
+    /// in parallel, see `Output::slice_2d`.
+    #[cfg(feature = "rayon")]
+    pub fn slice_2d_par<F>(
+        &self,
+        indices: (usize, usize),
+        radius: (A::Item, A::Item),
+        len: usize,
+        f: F,
+    ) -> Slice2<A::Item>
+    where
+        A: Send + Sync,
+        A::Item: Send + Sync,
+        F: Fn(&A) -> A::Item + Sync,
+    {
+        let (xs, ys) = (
+            self.grid(indices.0, radius.0, len),
+            self.grid(indices.1, radius.1, len),
+        );
+        let fs = self.plane(indices, &xs, &ys).par_iter().map(&f).collect();
+
+        Slice2 {
+            indices,
+            xs,
+            ys,
+            fs,
+        }
+    }
+
+    /// Gives `len` coordinate values evenly spaced within `radius`
+    /// of the estimation, both ends included.
+    fn grid(&self, index: usize, radius: A::Item, len: usize) -> Vec<A::Item> {
+        let center = self.x_min.as_ref()[index];
+        if len < 2 {
+            return vec![center; len];
+        }
+
+        let cast = |x: usize| <A::Item as NumCast>::from(x).unwrap();
+        let step = (radius + radius) / cast(len - 1);
+        (0..len)
+            .map(|k| step.mul_add(cast(k), center - radius))
+            .collect()
+    }
+
+    /// Gives the points of the grid `xs × ys`, row-major.
+    fn plane(&self, indices: (usize, usize), xs: &[A::Item], ys: &[A::Item]) -> Vec<A> {
+        xs.iter()
+            .flat_map(|&x| {
+                ys.iter()
+                    .map(move |&y| self.moved(&[(indices.0, x), (indices.1, y)]))
+            })
+            .collect()
+    }
+
+    /// Gives the estimation, with the coordinates `moves` set.
+    fn moved(&self, moves: &[(usize, A::Item)]) -> A {
+        let mut x = self.x_min.clone();
+        for &(index, value) in moves {
+            x.as_mut()[index] = value;
+        }
+        x
+    }
+}
@@ -1,7 +1,80 @@
+mod bayes;
+mod cache;
+mod cma;
+mod cobyla;
+#[cfg(feature = "num-complex")]
+mod complex;
+mod convergence;
+mod de;
+mod direct;
+mod evaluator;
+mod external;
+mod global;
+mod landscape;
+mod linalg;
 mod minimizer;
+mod multistart;
+mod objective;
+mod optimizer;
+mod polish;
+mod portfolio;
+mod precision;
+#[cfg(feature = "indicatif")]
+mod progress;
+mod pso;
+mod random;
+mod replay;
+mod report;
+mod rng;
+pub mod scipy;
 mod simplex;
+mod spsa;
+mod strategy;
+mod surrogate;
 #[cfg(test)]
 mod tests;
+mod tune;
 mod vector;
+mod weights;
 
+pub use crate::bayes::BayesianOptimization;
+pub use crate::cache::Cache;
+pub use crate::cma::CmaEs;
+pub use crate::cobyla::Cobyla;
+pub use crate::convergence::Convergence;
+pub use crate::de::DifferentialEvolution;
+pub use crate::direct::Direct;
+pub use crate::evaluator::AsyncEvaluator;
+pub use crate::evaluator::Evaluator;
+pub use crate::evaluator::Threads;
+pub use crate::external::External;
+pub use crate::external::ExternalError;
+pub use crate::external::ExternalInput;
+pub use crate::global::Phase;
+pub use crate::landscape::Slice;
+pub use crate::landscape::Slice2;
 pub use crate::minimizer::*;
+pub use crate::multistart::MultiStart;
+pub use crate::objective::Auxiliary;
+pub use crate::objective::BestSoFar;
+pub use crate::objective::Evaluation;
+pub use crate::objective::Lexicographic;
+pub use crate::objective::Move;
+pub use crate::objective::ObjectiveValue;
+pub use crate::objective::PanicPolicy;
+pub use crate::objective::Parameters;
+pub use crate::objective::Timing;
+pub use crate::optimizer::Method;
+pub use crate::optimizer::Optimizer;
+pub use crate::polish::Polish;
+pub use crate::portfolio::Portfolio;
+#[cfg(feature = "indicatif")]
+pub use crate::progress::progress;
+pub use crate::pso::ParticleSwarm;
+pub use crate::random::RandomSearch;
+pub use crate::report::Report;
+pub use crate::simplex::Workspace;
+pub use crate::spsa::Spsa;
+pub use crate::strategy::Step;
+pub use crate::strategy::Strategy;
+pub use crate::weights::Weights;
@@ -0,0 +1,277 @@
+use num_traits::Float;
+
+/// Solves the `n × n` row-major system `a x = b` in place.
+///
+/// Uses Gaussian elimination with partial pivoting,
+/// `b` holds the solution on success and `None` is returned if `a` is singular.
+pub(crate) fn solve<T: Float>(a: &mut [T], b: &mut [T]) -> Option<()> {
+    let n = b.len();
+    debug_assert_eq!(a.len(), n * n);
+
+    for col in 0..n {
+        // Pivot
+        let pivot = (col..n).max_by(|&i, &j| {
+            a[i * n + col]
+                .abs()
+                .partial_cmp(&a[j * n + col].abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })?;
+        if a[pivot * n + col].is_zero() || a[pivot * n + col].is_nan() {
+            return None;
+        }
+        if pivot != col {
+            for k in 0..n {
+                a.swap(pivot * n + k, col * n + k);
+            }
+            b.swap(pivot, col);
+        }
+
+        // Elimination
+        let inv = a[col * n + col].recip();
+        for row in col + 1..n {
+            let factor = a[row * n + col] * inv;
+            if factor.is_zero() {
+                continue;
+            }
+            for k in col..n {
+                a[row * n + k] = a[row * n + k] - factor * a[col * n + k];
+            }
+            b[row] = b[row] - factor * b[col];
+        }
+    }
+
+    // Back substitution
+    for row in (0..n).rev() {
+        let sum = (row + 1..n).fold(b[row], |acc, k| acc - a[row * n + k] * b[k]);
+        b[row] = sum / a[row * n + row];
+    }
+
+    if b.iter().all(|x| x.is_finite()) {
+        Some(())
+    } else {
+        None
+    }
+}
+
+/// Computes the Cholesky factor `l` of the `n × n` row-major matrix `a`
+/// in place, such that `a = l lᵀ`.
+///
+/// Only the lower triangle is read, the upper one is zeroed,
+/// and `None` is returned if `a` is not positive definite.
+pub(crate) fn cholesky<T: Float>(a: &mut [T], n: usize) -> Option<()> {
+    debug_assert_eq!(a.len(), n * n);
+
+    for j in 0..n {
+        let diag = (0..j).fold(a[j * n + j], |acc, k| acc - a[j * n + k] * a[j * n + k]);
+        if diag.is_nan() || diag <= T::zero() {
+            return None;
+        }
+        let diag = diag.sqrt();
+        a[j * n + j] = diag;
+
+        for i in j + 1..n {
+            let sum = (0..j).fold(a[i * n + j], |acc, k| acc - a[i * n + k] * a[j * n + k]);
+            a[i * n + j] = sum / diag;
+        }
+        for k in j + 1..n {
+            a[j * n + k] = T::zero();
+        }
+    }
+
+    Some(())
+}
+
+/// Diagonalizes the `n × n` row-major symmetric matrix `a`
+/// with the cyclic Jacobi method.
+///
+/// Gives the eigenvalues and the row-major matrix whose columns
+/// are the corresponding eigenvectors, `a` is overwritten.
+pub(crate) fn symmetric_eigen<T: Float>(a: &mut [T], n: usize) -> (Vec<T>, Vec<T>) {
+    debug_assert_eq!(a.len(), n * n);
+
+    let mut v = vec![T::zero(); n * n];
+    for idx in 0..n {
+        v[idx * n + idx] = T::one();
+    }
+
+    let two = T::one() + T::one();
+    for _ in 0..64 {
+        let off = (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .fold(T::zero(), |acc, (i, j)| acc + a[i * n + j] * a[i * n + j]);
+        let total = a.iter().fold(T::zero(), |acc, &x| acc + x * x);
+        if off <= T::epsilon() * T::epsilon() * total {
+            break;
+        }
+
+        for p in 0..n {
+            for q in p + 1..n {
+                let apq = a[p * n + q];
+                if apq.is_zero() {
+                    continue;
+                }
+
+                // Rotation annihilating a[p][q]
+                let theta = (a[q * n + q] - a[p * n + p]) / (two * apq);
+                let t = theta.signum() / (theta.abs() + theta.hypot(T::one()));
+                let c = t.hypot(T::one()).recip();
+                let s = t * c;
+
+                for k in 0..n {
+                    let (akp, akq) = (a[k * n + p], a[k * n + q]);
+                    a[k * n + p] = c * akp - s * akq;
+                    a[k * n + q] = s * akp + c * akq;
+                }
+                for k in 0..n {
+                    let (apk, aqk) = (a[p * n + k], a[q * n + k]);
+                    a[p * n + k] = c * apk - s * aqk;
+                    a[q * n + k] = s * apk + c * aqk;
+                }
+                for k in 0..n {
+                    let (vkp, vkq) = (v[k * n + p], v[k * n + q]);
+                    v[k * n + p] = c * vkp - s * vkq;
+                    v[k * n + q] = s * vkp + c * vkq;
+                }
+            }
+        }
+    }
+
+    let values = (0..n).map(|idx| a[idx * n + idx]).collect();
+    (values, v)
+}
+
+/// Minimizes `c x` subject to `a x <= b` and `x >= 0`,
+/// `a` being the `m × n` row-major constraint matrix.
+///
+/// Uses the two-phase simplex method with Bland's rule,
+/// `None` is returned if the problem is infeasible or unbounded.
+pub(crate) fn linprog<T: Float>(c: &[T], a: &[T], b: &[T]) -> Option<Vec<T>> {
+    let (n, m) = (c.len(), b.len());
+    debug_assert_eq!(a.len(), m * n);
+    let eps = T::epsilon() * T::from(1024).unwrap();
+
+    // Columns of the variables, the slacks, the artificials and the right-hand side
+    let artificials: Vec<usize> = (0..m).filter(|&i| b[i] < T::zero()).collect();
+    let width = n + m + artificials.len() + 1;
+    let rhs = width - 1;
+    let mut tableau = vec![T::zero(); m * width];
+    let mut basis: Vec<usize> = (n..n + m).collect();
+    for i in 0..m {
+        let sign = if b[i] < T::zero() {
+            -T::one()
+        } else {
+            T::one()
+        };
+        let row = &mut tableau[i * width..(i + 1) * width];
+        for j in 0..n {
+            row[j] = sign * a[i * n + j];
+        }
+        row[n + i] = sign;
+        row[rhs] = sign * b[i];
+    }
+    for (k, &i) in artificials.iter().enumerate() {
+        tableau[i * width + n + m + k] = T::one();
+        basis[i] = n + m + k;
+    }
+
+    // Phase 1, driving the artificials to zero
+    if !artificials.is_empty() {
+        let mut cost = vec![T::zero(); rhs];
+        cost[n + m..].iter_mut().for_each(|cj| *cj = T::one());
+        pivots(&mut tableau, &mut basis, width, &cost, rhs, eps)?;
+
+        let scale = b.iter().fold(T::one(), |acc, bi| acc.max(bi.abs()));
+        let infeasibility = (0..m)
+            .filter(|&i| basis[i] >= n + m)
+            .fold(T::zero(), |acc, i| acc + tableau[i * width + rhs]);
+        if infeasibility > eps * scale {
+            return None;
+        }
+
+        // Artificials left in the basis at zero
+        for i in 0..m {
+            if basis[i] >= n + m {
+                if let Some(j) = (0..n + m).find(|&j| tableau[i * width + j].abs() > eps) {
+                    pivot(&mut tableau, &mut basis, width, i, j);
+                }
+            }
+        }
+    }
+
+    // Phase 2, the artificials being left out
+    let mut cost = vec![T::zero(); n + m];
+    cost[..n].copy_from_slice(c);
+    pivots(&mut tableau, &mut basis, width, &cost, n + m, eps)?;
+
+    let mut x = vec![T::zero(); n];
+    for (i, &j) in basis.iter().enumerate() {
+        if j < n {
+            x[j] = tableau[i * width + rhs];
+        }
+    }
+    Some(x)
+}
+
+/// Pivots until the reduced costs of the first `cols` columns are nonnegative.
+fn pivots<T: Float>(
+    tableau: &mut [T],
+    basis: &mut [usize],
+    width: usize,
+    cost: &[T],
+    cols: usize,
+    eps: T,
+) -> Option<()> {
+    let m = basis.len();
+    let rhs = width - 1;
+    let cost_of = |j: usize| cost.get(j).copied().unwrap_or_else(T::zero);
+
+    for _ in 0..64 * width {
+        // Entering column, the first one with a negative reduced cost
+        let entering = (0..cols).find(|&j| {
+            let reduced = (0..m).fold(cost[j], |acc, i| {
+                acc - cost_of(basis[i]) * tableau[i * width + j]
+            });
+            reduced < -eps
+        });
+        let col = match entering {
+            Some(col) => col,
+            None => return Some(()),
+        };
+
+        // Leaving row, by the minimum ratio test
+        let row = (0..m)
+            .filter(|&i| tableau[i * width + col] > eps)
+            .min_by(|&i, &k| {
+                let ratio_i = tableau[i * width + rhs] / tableau[i * width + col];
+                let ratio_k = tableau[k * width + rhs] / tableau[k * width + col];
+                ratio_i
+                    .partial_cmp(&ratio_k)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then(basis[i].cmp(&basis[k]))
+            })?;
+
+        pivot(tableau, basis, width, row, col);
+    }
+
+    Some(())
+}
+
+/// Brings the column `col` into the basis in place of the row `row`.
+fn pivot<T: Float>(tableau: &mut [T], basis: &mut [usize], width: usize, row: usize, col: usize) {
+    let inv = tableau[row * width + col].recip();
+    for k in 0..width {
+        tableau[row * width + k] = tableau[row * width + k] * inv;
+    }
+
+    for i in 0..basis.len() {
+        let factor = tableau[i * width + col];
+        if i == row || factor.is_zero() {
+            continue;
+        }
+        for k in 0..width {
+            tableau[i * width + k] = tableau[i * width + k] - factor * tableau[row * width + k];
+        }
+    }
+
+    basis[row] = col;
+}
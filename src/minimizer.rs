@@ -1,61 +1,310 @@
 use array::Array;
 use num_traits::Float;
 use num_traits::NumCast;
+use num_traits::One;
+use num_traits::Zero;
 
+use crate::global::Phase;
+use crate::linalg;
+use crate::objective::Auxiliary;
+use crate::objective::BestSoFar;
+use crate::objective::Evaluation;
+use crate::objective::Function;
+use crate::objective::Lexicographic;
+use crate::objective::Move;
+use crate::objective::Objective;
+use crate::objective::ObjectiveValue;
+use crate::objective::PanicPolicy;
+use crate::objective::Parameters;
+use crate::objective::Timing;
+use crate::polish;
+use crate::polish::Polish;
 use crate::simplex::Pair;
 use crate::simplex::Simplex;
+use crate::simplex::Workspace;
+use crate::strategy::Step;
+use crate::strategy::Strategy;
+use crate::surrogate;
+use crate::vector::Vector;
 
 use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::Duration;
 
-/// Maximal iteration reached.
-#[derive(Debug)]
-pub struct MaxIterError(pub usize);
+/// Number of best points kept to report a divergence.
+const TAIL_LEN: usize = 10;
+
+/// Minimization failure.
+pub enum Error<A: Array, V = <A as Array>::Item> {
+    /// Maximal iteration reached.
+    MaxIter { limit: MaxIter, iter: usize },
+    /// Maximal function evaluation reached.
+    MaxFev(usize),
+    /// Maximal wall-clock time reached.
+    MaxTime(Duration),
+    /// Unbounded objective, with the last best points.
+    ///
+    /// It is detected once the best value falls at each of the last
+    /// iterations while the simplex keeps growing, far beyond its first size.
+    Diverged(Vec<Point<A, V>>),
+    /// Panicking function, with the point and the panic message.
+    Panicked { x: A, message: String },
+    /// Global method run without box bounds.
+    MissingBounds,
+}
+
+impl<A: Array + Debug, V: Debug> Debug for Error<A, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MaxIter { limit, iter } => f
+                .debug_struct("MaxIter")
+                .field("limit", limit)
+                .field("iter", iter)
+                .finish(),
+            Error::MaxFev(max_fev) => f.debug_tuple("MaxFev").field(max_fev).finish(),
+            Error::MaxTime(max_time) => f.debug_tuple("MaxTime").field(max_time).finish(),
+            Error::Diverged(tail) => f.debug_tuple("Diverged").field(tail).finish(),
+            Error::Panicked { x, message } => f
+                .debug_struct("Panicked")
+                .field("x", x)
+                .field("message", message)
+                .finish(),
+            Error::MissingBounds => f.write_str("MissingBounds"),
+        }
+    }
+}
 
-impl std::fmt::Display for MaxIterError {
+impl<A: Array, V> std::fmt::Display for Error<A, V> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Maximal iteration ({}) reached", self.0)
+        match self {
+            Error::MaxIter { limit, iter } => match limit {
+                MaxIter::PerDimension(per_dim) => write!(
+                    f,
+                    "Maximal iteration ({}, {} per dimension) reached",
+                    iter, per_dim
+                ),
+                MaxIter::Absolute(_) => write!(f, "Maximal iteration ({}) reached", iter),
+            },
+            Error::MaxFev(max_fev) => {
+                write!(f, "Maximal function evaluation ({}) reached", max_fev)
+            }
+            Error::MaxTime(max_time) => write!(f, "Maximal time ({:?}) reached", max_time),
+            Error::Diverged(_) => write!(f, "Minimization diverged"),
+            Error::Panicked { message, .. } => write!(f, "Function panicked: {}", message),
+            Error::MissingBounds => write!(f, "Box bounds required by global methods"),
+        }
+    }
+}
+
+impl<A: Array + Debug, V: Debug> std::error::Error for Error<A, V> {}
+
+/// Termination reason.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Termination {
+    /// Function value and point tolerances reached.
+    Tolerance,
+    /// Simplex gradient tolerance reached.
+    Gradient,
+    /// Simplex small enough for the polish phase.
+    Handoff,
+    /// Consecutive shrink limit reached.
+    Shrinks,
+    /// Same function value on every vertex for several iterations.
+    FlatObjective,
+    /// Iteration schedule completed, such as the SPSA one.
+    Iterations,
+}
+
+impl std::fmt::Display for Termination {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Termination::Tolerance => write!(f, "Function value and point tolerances reached"),
+            Termination::Gradient => write!(f, "Simplex gradient tolerance reached"),
+            Termination::Handoff => write!(f, "Simplex small enough for the polish phase"),
+            Termination::Shrinks => write!(f, "Consecutive shrink limit reached"),
+            Termination::FlatObjective => write!(f, "Function flat on the simplex"),
+            Termination::Iterations => write!(f, "Iteration schedule completed"),
+        }
+    }
+}
+
+/// Reaction to repeated shrinks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShrinkReaction {
+    /// Stops with the `Shrinks` termination reason.
+    Terminate,
+    /// Rebuilds the simplex around the best vertex.
+    Restart,
+}
+
+/// Iteration limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaxIter {
+    /// Limit multiplied by the dimension.
+    PerDimension(usize),
+    /// Limit regardless of the dimension.
+    Absolute(usize),
+}
+
+impl MaxIter {
+    /// Gives the number of iterations allowed in dimension `dim`.
+    pub fn limit(self, dim: usize) -> usize {
+        match self {
+            MaxIter::PerDimension(max_iter) => dim.saturating_mul(max_iter),
+            MaxIter::Absolute(max_iter) => max_iter,
+        }
     }
 }
 
-impl std::error::Error for MaxIterError {}
+/// Expected scales and correlations of the parameters,
+/// as a row-major `n × n` matrix.
+#[derive(Debug, Clone, Copy)]
+pub enum Shape<'a, T> {
+    /// Covariance matrix.
+    Covariance(&'a [T]),
+    /// Lower-triangular Cholesky factor of the covariance matrix.
+    Cholesky(&'a [T]),
+}
+
+/// A point and its function value.
+#[derive(Debug, Clone)]
+pub struct Point<A: Array, V = <A as Array>::Item> {
+    pub f: V,
+    pub x: A,
+}
 
 /// Output data.
 #[derive(Debug)]
-pub struct Output<A: Array> {
-    pub f_min: A::Item,
+pub struct Output<A: Array, V = <A as Array>::Item> {
+    pub f_min: V,
     pub x_min: A,
     pub iter: usize,
+    pub fev: usize,
+    pub best_ever: Point<A, V>,
+    pub termination: Termination,
+    pub spread: A,
+    pub log: Vec<Evaluation<A, V>>,
+    pub phase: Phase,
+    pub timing: Option<Timing>,
+
+    /// Minimizer and minimum of a quadratic fitted on the final simplex.
+    pub fitted: Option<Point<A, A::Item>>,
+
+    /// Best point at each improvement, when recorded.
+    pub trajectory: Vec<Point<A, V>>,
 }
 
-pub type Result<A> = std::result::Result<Output<A>, MaxIterError>;
+pub type Result<A, V = <A as Array>::Item> = std::result::Result<Output<A, V>, Error<A, V>>;
+
+impl<A: Array, V> Output<A, V> {
+    /// Gives the typed parameters of the estimation, such as `Weights`,
+    /// see `Minimizer::minimize_typed`.
+    pub fn parameters<P: Parameters<A::Item>>(&self) -> P {
+        P::from_scalars(self.x_min.as_ref())
+    }
+}
 
 /// A structure that holds all the minimization parameters.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Minimizer<A: Array> {
     // Reflection parameter
-    a: A::Item,
+    pub(crate) a: A::Item,
 
     // Contraction parameter
-    b: A::Item,
+    pub(crate) b: A::Item,
 
     // Expansion parameter
-    c: A::Item,
+    pub(crate) c: A::Item,
 
     // Shrinkage parameter
     pub(crate) d: A::Item,
 
+    // Dimension-dependent parameters
+    pub(crate) adaptive: bool,
+
     // Initialization parameters
     pub(crate) step: A::Item,
     pub(crate) step_zero: A::Item,
 
     // Tolerance (function) parameter
-    tol_f: A::Item,
+    pub(crate) tol_f: A::Item,
 
     // Tolerance (point) parameter
-    tol_x: A::Item,
+    pub(crate) tol_x: A::Item,
+
+    // Tolerance (point, per coordinate) parameter
+    pub(crate) tol_x_each: Option<Vec<A::Item>>,
+
+    // Tolerance (point, over every vertex) parameter
+    pub(crate) tol_x_all: bool,
+
+    // Tolerance (simplex gradient) parameter
+    tol_grad: Option<A::Item>,
 
     // Iterations parameter
-    max_iter: usize,
+    pub(crate) max_iter: MaxIter,
+
+    // Function evaluations parameter
+    pub(crate) max_fev: Option<usize>,
+
+    // Wall-clock time parameter
+    pub(crate) max_time: Option<Duration>,
+
+    // Divergence parameter
+    max_x: A::Item,
+
+    // Trust radius parameter
+    max_step: Option<A::Item>,
+
+    // Evaluation log parameter
+    record: bool,
+
+    // Evaluation timing parameter
+    timing: bool,
+
+    // Panic handling parameter
+    pub(crate) catch_panics: Option<PanicPolicy>,
+
+    // Shared best point parameter
+    best_so_far: Option<Arc<BestSoFar<A>>>,
+
+    // Surrogate proposal period
+    surrogate: Option<usize>,
+
+    // Quasi-Newton refinement parameters
+    polish: Option<Polish<A::Item>>,
+
+    // Restart parameter
+    restarts: usize,
+
+    // Repeated shrink safeguard parameter
+    max_shrinks: Option<(usize, ShrinkReaction)>,
+
+    // Flat landscape detection parameter
+    flat_iter: Option<usize>,
+
+    // Quadratic fit parameter
+    fit: bool,
+
+    // Best vertex re-evaluation parameter
+    reevaluate: bool,
+
+    // Best point trajectory parameter
+    trajectory: bool,
+
+    // Candidate generation parameter
+    strategy: Option<Arc<dyn Strategy<A>>>,
+
+    // Speculative evaluation parameter
+    speculative: bool,
+}
+
+/// Converts a tolerance, which cannot be finer than the machine epsilon
+/// of low precision types such as `f16`.
+pub(crate) fn tolerance<T: Float>(tol: f64) -> T {
+    <T as NumCast>::from(tol).unwrap().max(T::epsilon())
 }
 
 impl<A: Array> Default for Minimizer<A>
@@ -68,11 +317,33 @@ where
             b: <A::Item as NumCast>::from(0.5).unwrap(),
             c: <A::Item as NumCast>::from(2.0).unwrap(),
             d: <A::Item as NumCast>::from(0.5).unwrap(),
+            adaptive: false,
             step: <A::Item as NumCast>::from(0.01).unwrap(),
             step_zero: <A::Item as NumCast>::from(0.00025).unwrap(),
-            tol_f: <A::Item as NumCast>::from(1e-4).unwrap(),
-            tol_x: <A::Item as NumCast>::from(1e-4).unwrap(),
-            max_iter: 200,
+            tol_f: tolerance(1e-4),
+            tol_x: tolerance(1e-4),
+            tol_x_each: None,
+            tol_x_all: false,
+            tol_grad: None,
+            max_iter: MaxIter::PerDimension(200),
+            max_fev: None,
+            max_time: None,
+            max_x: A::Item::infinity(),
+            max_step: None,
+            record: false,
+            timing: false,
+            catch_panics: None,
+            best_so_far: None,
+            surrogate: None,
+            polish: None,
+            restarts: 0,
+            max_shrinks: None,
+            flat_iter: None,
+            fit: false,
+            reevaluate: false,
+            trajectory: false,
+            strategy: None,
+            speculative: false,
         }
     }
 }
@@ -81,111 +352,1056 @@ impl<A: Array> Minimizer<A>
 where
     A::Item: Float,
 {
+    /// Loose tolerances and a small budget, for a quick estimation.
+    pub fn fast() -> Minimizer<A> {
+        Minimizer {
+            tol_f: tolerance(1e-3),
+            tol_x: tolerance(1e-3),
+            max_iter: MaxIter::PerDimension(50),
+            ..Minimizer::default()
+        }
+    }
+
+    /// Tight tolerances, adaptive parameters and restarts,
+    /// for an estimation as precise as possible.
+    pub fn accurate() -> Minimizer<A> {
+        Minimizer {
+            adaptive: true,
+            tol_f: tolerance(1e-10),
+            tol_x: tolerance(1e-8),
+            max_iter: MaxIter::PerDimension(1000),
+            restarts: 2,
+            ..Minimizer::default()
+        }
+    }
+
+    /// Large initial simplex, loose function tolerance and re-evaluation
+    /// of the best vertex, for noisy functions.
+    pub fn robust() -> Minimizer<A> {
+        Minimizer {
+            step: <A::Item as NumCast>::from(0.1).unwrap(),
+            step_zero: <A::Item as NumCast>::from(0.025).unwrap(),
+            tol_f: tolerance(1e-2),
+            restarts: 1,
+            reevaluate: true,
+            ..Minimizer::default()
+        }
+    }
+
+    /// Sets the coordinate magnitude of a vertex
+    /// beyond which the minimization diverges.
+    pub fn max_x(mut self, max_x: A::Item) -> Minimizer<A> {
+        self.max_x = max_x;
+        self
+    }
+
+    /// Sets the trust radius: reflection and expansion points farther
+    /// than `max_step` from the centroid are pulled back onto it.
+    pub fn max_step(mut self, max_step: A::Item) -> Minimizer<A> {
+        assert!(max_step > A::Item::zero(), "trust radius must be positive");
+        self.max_step = Some(max_step);
+        self
+    }
+
+    /// Sets a point tolerance per coordinate, replacing the global one,
+    /// for coordinates of different scales.
+    pub fn tol_x_each(mut self, tol_x: &[A::Item]) -> Minimizer<A> {
+        self.tol_x_each = Some(tol_x.to_vec());
+        self
+    }
+
+    /// Enables the simplex gradient termination test.
+    ///
+    /// The minimization stops once the norm of the simplex gradient times
+    /// the simplex diameter, a first-order estimate of the function variation
+    /// across the simplex, falls below `tol_grad`.
+    pub fn tol_grad(mut self, tol_grad: A::Item) -> Minimizer<A> {
+        self.tol_grad = Some(tol_grad);
+        self
+    }
+
+    /// Records every function evaluation in the output log.
+    pub fn record(mut self, record: bool) -> Minimizer<A> {
+        self.record = record;
+        self
+    }
+
+    /// Times every function evaluation, see `Output::timing`.
+    pub fn timing(mut self, timing: bool) -> Minimizer<A> {
+        self.timing = timing;
+        self
+    }
+
+    /// Catches the panics of the function, handling them according to `policy`.
+    ///
+    /// The panic message is still printed by the panic hook.
+    pub fn catch_panics(mut self, policy: PanicPolicy) -> Minimizer<A> {
+        self.catch_panics = Some(policy);
+        self
+    }
+
+    /// Publishes the best point to `handle` as the minimization goes,
+    /// for other threads to poll.
+    ///
+    /// Minimizations sharing the handle, such as the starts of
+    /// `Minimizer::minimize_multistart`, publish their best point only
+    /// if it improves on the stored one, and add up their evaluations.
+    /// The handle holding scalar values, it is left untouched by the
+    /// minimizations of other values, see `Minimizer::minimize_ordered`.
+    pub fn best_so_far(mut self, handle: Arc<BestSoFar<A>>) -> Minimizer<A> {
+        self.best_so_far = Some(handle);
+        self
+    }
+
+    /// Proposes the minimizer of a quadratic model of the function
+    /// every `period` iterations.
+    ///
+    /// The model is fitted on the most recent evaluations
+    /// and its minimizer replaces the worst vertex only if it improves on the best one.
+    pub fn surrogate(mut self, period: usize) -> Minimizer<A> {
+        assert!(period > 0, "the surrogate period must be positive");
+        self.surrogate = Some(period);
+        self
+    }
+
+    /// Hands off to a quasi-Newton refinement once the simplex is small.
+    ///
+    /// The refined point is kept only if it improves on the best vertex.
+    pub fn polish(mut self, polish: Polish<A::Item>) -> Minimizer<A> {
+        self.polish = Some(polish);
+        self
+    }
+
+    /// Restarts up to `restarts` times from the best vertex once converged,
+    /// as long as the previous restart improved the estimation.
+    pub fn restarts(mut self, restarts: usize) -> Minimizer<A> {
+        self.restarts = restarts;
+        self
+    }
+
+    /// Reacts with `reaction` once `limit` shrinks happen in a row,
+    /// which on noisy functions collapse the simplex without converging.
+    pub fn max_consecutive_shrinks(
+        mut self,
+        limit: usize,
+        reaction: ShrinkReaction,
+    ) -> Minimizer<A> {
+        assert!(limit > 0, "shrink limit must be positive");
+        self.max_shrinks = Some((limit, reaction));
+        self
+    }
+
+    /// Stops once every vertex has the same function value,
+    /// up to the machine epsilon, for `iterations` iterations in a row.
+    ///
+    /// It usually reveals a function ignoring its inputs,
+    /// or quantized outputs.
+    pub fn flat_iter(mut self, iterations: usize) -> Minimizer<A> {
+        assert!(iterations > 0, "flat iteration count must be positive");
+        self.flat_iter = Some(iterations);
+        self
+    }
+
+    /// Fits a quadratic on the final simplex vertices and edge midpoints,
+    /// see `Output::fitted`.
+    ///
+    /// It costs `n (n + 1) / 2` evaluations, for roughly an extra digit.
+    pub fn fit(mut self, fit: bool) -> Minimizer<A> {
+        self.fit = fit;
+        self
+    }
+
+    /// Re-evaluates the best vertex at each iteration,
+    /// so that a lucky evaluation of a noisy function does not stick.
+    pub fn reevaluate(mut self, reevaluate: bool) -> Minimizer<A> {
+        self.reevaluate = reevaluate;
+        self
+    }
+
+    /// Records the best point at each improvement, see `Output::trajectory`.
+    ///
+    /// It is much lighter than the full evaluation log,
+    /// to plot the search path for instance.
+    pub fn trajectory(mut self, trajectory: bool) -> Minimizer<A> {
+        self.trajectory = trajectory;
+        self
+    }
+
+    /// Replaces the reflection, expansion, contraction and shrinkage step
+    /// with the one of `strategy`, for Nelder-Mead variants.
+    pub fn strategy<S: Strategy<A> + 'static>(mut self, strategy: S) -> Minimizer<A> {
+        self.strategy = Some(Arc::new(strategy));
+        self
+    }
+
+    /// Evaluates the reflection, expansion and contraction points
+    /// of each iteration as one batch, see `Minimizer::minimize_with`.
+    ///
+    /// It spends up to three extra evaluations per iteration,
+    /// for a single round trip to a remote evaluator.
+    pub fn speculative(mut self, speculative: bool) -> Minimizer<A> {
+        self.speculative = speculative;
+        self
+    }
+
+    /// Sets the iteration limit.
+    pub fn max_iter(mut self, max_iter: MaxIter) -> Minimizer<A> {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Sets the function evaluation limit.
+    pub fn max_fev(mut self, max_fev: usize) -> Minimizer<A> {
+        self.max_fev = Some(max_fev);
+        self
+    }
+
+    /// Sets the wall-clock time limit, checked at each iteration.
+    pub fn max_time(mut self, max_time: Duration) -> Minimizer<A> {
+        self.max_time = Some(max_time);
+        self
+    }
+
+    /// Converts the parameters to another scalar type.
+    ///
+    /// The tolerances and the finite-difference step of the polish phase
+    /// cannot be finer than the precision of the new type allows,
+    /// and the shared best point handle and the strategy are dropped.
+    pub fn cast<B: Array>(&self) -> Minimizer<B>
+    where
+        B::Item: Float,
+    {
+        let cast = |x: A::Item| <B::Item as NumCast>::from(x).unwrap();
+        let tol = |x: A::Item| cast(x).max(B::Item::epsilon());
+
+        Minimizer {
+            a: cast(self.a),
+            b: cast(self.b),
+            c: cast(self.c),
+            d: cast(self.d),
+            adaptive: self.adaptive,
+            step: cast(self.step),
+            step_zero: cast(self.step_zero),
+            tol_f: tol(self.tol_f),
+            tol_x: tol(self.tol_x),
+            tol_x_each: self
+                .tol_x_each
+                .as_ref()
+                .map(|tol_x| tol_x.iter().map(|&x| tol(x)).collect()),
+            tol_x_all: self.tol_x_all,
+            tol_grad: self.tol_grad.map(tol),
+            max_iter: self.max_iter,
+            max_fev: self.max_fev,
+            max_time: self.max_time,
+            max_x: cast(self.max_x),
+            max_step: self.max_step.map(cast),
+            record: self.record,
+            timing: self.timing,
+            catch_panics: self.catch_panics,
+            best_so_far: None,
+            surrogate: self.surrogate,
+            polish: self.polish.map(|polish| Polish {
+                threshold: cast(polish.threshold),
+                fd_step: cast(polish.fd_step).max(B::Item::epsilon().cbrt()),
+                max_iter: polish.max_iter,
+            }),
+            restarts: self.restarts,
+            max_shrinks: self.max_shrinks,
+            flat_iter: self.flat_iter,
+            fit: self.fit,
+            reevaluate: self.reevaluate,
+            trajectory: self.trajectory,
+            strategy: None,
+            speculative: self.speculative,
+        }
+    }
+
     /// Minimizes the function `f` with the seed `x0`.
-    pub fn minimize<F>(&self, x0: &[A::Item], mut f: F) -> Result<A>
+    pub fn minimize<F>(&self, x0: &[A::Item], f: F) -> Result<A>
     where
         F: FnMut(&A) -> A::Item,
         A::Item: Clone,
+    {
+        self.minimize_owned(Vector::from_slice(x0).0, f)
+    }
+
+    /// Minimizes the function `f` with the seed `x0`,
+    /// which becomes the first vertex as is.
+    pub fn minimize_owned<F>(&self, x0: A, f: F) -> Result<A>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        self.start(x0, f)
+    }
+
+    /// Minimizes the function `f`, evaluated point by point
+    /// or batch by batch, with the seed `x0`.
+    pub(crate) fn start<F>(&self, x0: A, f: F) -> Result<A>
+    where
+        F: Function<A, A::Item>,
     {
         // Init
-        let max_iter = x0.len() * self.max_iter;
-        let mut simplex = Simplex::new(x0, &mut f, self);
+        let mut objective = self.shared(self.objective(f, x0.as_ref().len()));
+        let mut simplex = Simplex::new(
+            Vector(x0),
+            None,
+            |xs: &[A]| objective.call_batch(xs, Move::Init),
+            self,
+        );
 
-        // Sort
-        simplex.sort_unstable();
+        self.iterate(&mut simplex, objective)
+    }
 
-        for iter in 0..max_iter {
-            // Centroid
-            let centroid = simplex.centroid();
+    /// Minimizes the function `f` with the seed `x0`,
+    /// building the simplex in the storage of `workspace`.
+    ///
+    /// Reusing the workspace spares the allocation of the vertices
+    /// and of the batches of points, such as the shrunk vertices,
+    /// when minimizing many functions of the same dimension.
+    /// The values of a batch and the trial points of an iteration
+    /// are still built anew, which allocates only for heap arrays.
+    pub fn minimize_in<F>(&self, workspace: &mut Workspace<A>, x0: &[A::Item], f: F) -> Result<A>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        // Init
+        let mut objective = self.shared(self.objective(f, x0.len()));
+        let mut simplex = Simplex::with_storage(
+            workspace.take(),
+            Vector::from_slice(x0),
+            None,
+            |xs: &[A]| objective.call_batch(xs, Move::Init),
+            self,
+        );
+
+        let result = self.iterate(&mut simplex, objective);
+        workspace.give(simplex.into_storage());
+        result
+    }
+
+    /// Minimizes the function `f` with the seed `x0`,
+    /// whose value `f0` is already known and not evaluated again.
+    pub fn minimize_with_f0<F>(&self, x0: &[A::Item], f0: A::Item, f: F) -> Result<A>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        // Init
+        let mut objective = self.shared(self.objective(f, x0.len()));
+        let x0 = Vector::<A>::from_slice(x0);
+        objective.known(&x0.0, f0);
+        let mut simplex = Simplex::new(
+            x0,
+            Some(f0),
+            |xs: &[A]| objective.call_batch(xs, Move::Init),
+            self,
+        );
 
-            // Best
-            let fb = simplex.best().unwrap().f;
+        self.iterate(&mut simplex, objective)
+    }
 
-            // Worst
-            let mut worst = simplex.worst().cloned().unwrap();
+    /// Minimizes the function `f` with the seed `x0`, `f` giving
+    /// its value and auxiliary data kept alongside the points.
+    ///
+    /// The data is cloned for every point kept, such as the best one.
+    /// As in `Minimizer::minimize_ordered`, the shared best point handle
+    /// is not updated.
+    pub fn minimize_aux<X, F>(&self, x0: &[A::Item], mut f: F) -> Result<A, Auxiliary<A::Item, X>>
+    where
+        X: Clone + Default,
+        F: FnMut(&A) -> (A::Item, X),
+    {
+        self.minimize_ordered(x0, |x: &A| {
+            let (f, aux) = f(x);
+            Auxiliary { f, aux }
+        })
+    }
 
-            // Reflection
-            let reflect = {
-                let x = &centroid + (&centroid - &worst.x) * self.a;
-                Pair::new(f(&x), x)
-            };
+    /// Minimizes the function `f` of typed parameters with the seed `x0`.
+    ///
+    /// The conversions happen at each evaluation, the output holds
+    /// the scalar coordinates, see `Parameters::from_scalars`.
+    pub fn minimize_typed<P, F>(&self, x0: &P, mut f: F) -> Result<A>
+    where
+        P: Parameters<A::Item>,
+        F: FnMut(&P) -> A::Item,
+    {
+        self.minimize(&x0.to_scalars(), |x: &A| f(&P::from_scalars(x.as_ref())))
+    }
+
+    /// Minimizes the function `f` over the coordinates `indices` of `base` only,
+    /// the others being kept fixed.
+    ///
+    /// The simplex lives in the subspace, a single full-size buffer being
+    /// updated for each evaluation, so the output coordinates follow `indices`.
+    /// No dense vector of the full dimension is built by the vertex operations,
+    /// as a sparse array type would do it, but `Array` values are built from
+    /// their items alone, leaving no room for the indices.
+    pub fn minimize_subset<F>(&self, base: &[A::Item], indices: &[usize], mut f: F) -> Result<A>
+    where
+        F: FnMut(&[A::Item]) -> A::Item,
+    {
+        let x0: Vec<_> = indices.iter().map(|&idx| base[idx]).collect();
+        let mut full = base.to_vec();
+
+        self.minimize(&x0, |x: &A| {
+            for (&idx, &xi) in indices.iter().zip(x.as_ref()) {
+                full[idx] = xi;
+            }
+            f(&full)
+        })
+    }
 
-            // Second-worst
-            let fs = simplex.second_worst().unwrap().f;
+    /// Minimizes the function `f` with the seed `x0`,
+    /// the vertices being ordered by the values of `f`.
+    ///
+    /// The tolerance tests, the surrogate and the polish phase
+    /// use their scalar, see `ObjectiveValue`. The shared best point
+    /// handle is not updated, the scalar not ordering the points.
+    pub fn minimize_ordered<V, F>(&self, x0: &[A::Item], f: F) -> Result<A, V>
+    where
+        V: ObjectiveValue<A::Item>,
+        F: FnMut(&A) -> V,
+    {
+        // Init
+        let mut objective = self.objective(f, x0.len());
+        let mut simplex = Simplex::new(
+            Vector::from_slice(x0),
+            None,
+            |xs: &[A]| objective.call_batch(xs, Move::Init),
+            self,
+        );
 
-            // Reflection accepted
-            if reflect.f < fs {
-                worst = reflect.clone();
+        self.iterate(&mut simplex, objective)
+    }
+
+    /// Minimizes the primary value of the function `f` with the seed `x0`,
+    /// breaking ties on the secondary one.
+    ///
+    /// As in `Minimizer::minimize_ordered`, the shared best point handle
+    /// is not updated.
+    pub fn minimize_lexicographic<F>(
+        &self,
+        x0: &[A::Item],
+        mut f: F,
+    ) -> Result<A, Lexicographic<A::Item>>
+    where
+        F: FnMut(&A) -> (A::Item, A::Item),
+    {
+        self.minimize_ordered(x0, |x: &A| {
+            let (primary, secondary) = f(x);
+            Lexicographic { primary, secondary }
+        })
+    }
+
+    /// Minimizes the function `f` starting from the simplex `vertices`.
+    ///
+    /// It takes from `1` to `n + 1` vertices of dimension `n`,
+    /// the missing ones are generated around their centroid.
+    pub fn minimize_simplex<V, F>(&self, vertices: &[V], f: F) -> Result<A>
+    where
+        V: AsRef<[A::Item]>,
+        F: FnMut(&A) -> A::Item,
+    {
+        // Init
+        let dim = vertices.first().map_or(0, |vertex| vertex.as_ref().len());
+        let mut objective = self.shared(self.objective(f, dim));
+        let mut simplex = Simplex::from_vertices(
+            vertices,
+            |xs: &[A]| objective.call_batch(xs, Move::Init),
+            self,
+        );
 
-                // Expansion
-                if reflect.f < fb {
-                    let expan = {
-                        let x = &centroid + (reflect.x - &centroid) * self.c;
-                        Pair::new(f(&x), x)
-                    };
+        self.iterate(&mut simplex, objective)
+    }
 
-                    // Expansion accepted
-                    if expan.f < fb {
-                        worst = expan;
+    /// Minimizes the function `f` with the seed `x0`,
+    /// building the initial simplex along the principal directions of `shape`.
+    ///
+    /// Each vertex lies one standard deviation away from the seed,
+    /// the covariance matrix must be positive definite.
+    pub fn minimize_shaped<F>(&self, x0: &[A::Item], shape: Shape<'_, A::Item>, f: F) -> Result<A>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        let dim = x0.len();
+        let mut cov = match shape {
+            Shape::Covariance(cov) => cov.to_vec(),
+            Shape::Cholesky(l) => {
+                assert_eq!(l.len(), dim * dim, "Cholesky factor dimension mismatch");
+                let mut cov = vec![A::Item::zero(); dim * dim];
+                for i in 0..dim {
+                    for j in 0..dim {
+                        cov[i * dim + j] = (0..=i.min(j)).fold(A::Item::zero(), |acc, k| {
+                            l[i * dim + k].mul_add(l[j * dim + k], acc)
+                        });
                     }
                 }
+                cov
+            }
+        };
+        assert_eq!(cov.len(), dim * dim, "covariance dimension mismatch");
+
+        let (values, vectors) = linalg::symmetric_eigen(&mut cov, dim);
+        assert!(
+            values.iter().all(|&value| value > A::Item::zero()),
+            "covariance must be positive definite"
+        );
+
+        let mut vertices = vec![x0.to_vec()];
+        for (k, value) in values.iter().enumerate() {
+            let std_dev = value.sqrt();
+            let vertex = x0
+                .iter()
+                .enumerate()
+                .map(|(i, &xi)| vectors[i * dim + k].mul_add(std_dev, xi))
+                .collect();
+            vertices.push(vertex);
+        }
+
+        self.minimize_simplex(&vertices, f)
+    }
+
+    fn objective<F, V>(&self, f: F, dim: usize) -> Objective<A, F, V> {
+        let recent_len = match self.surrogate {
+            Some(_) => 2 * surrogate::len(dim),
+            None => 0,
+        };
+        let mut objective = Objective::new(f, self.record, recent_len);
+        if self.timing {
+            objective = objective.timed();
+        }
+        if self.trajectory {
+            objective = objective.tracked();
+        }
+        if let Some(policy) = self.catch_panics {
+            objective = objective.catching(policy);
+        }
+        objective
+    }
+
+    /// Publishes the best point of `objective` to the shared handle, if any.
+    fn shared<F>(&self, objective: Objective<A, F>) -> Objective<A, F> {
+        match &self.best_so_far {
+            Some(handle) => objective.sharing(handle.clone()),
+            None => objective,
+        }
+    }
+
+    /// Gives the reflection, contraction, expansion and shrinkage parameters.
+    fn coefficients(&self, dim: usize) -> (A::Item, A::Item, A::Item, A::Item) {
+        if !self.adaptive {
+            return (self.a, self.b, self.c, self.d);
+        }
+
+        // Gao, F. and Han, L. (2012)
+        let one = A::Item::one();
+        let half = <A::Item as NumCast>::from(0.5).unwrap();
+        let dim = <A::Item as NumCast>::from(dim).unwrap();
+        let inv_dim = dim.recip();
+        let a = one;
+        let b = <A::Item as NumCast>::from(0.75).unwrap() - half * inv_dim;
+        let c = one + (one + one) * inv_dim;
+        let d = one - inv_dim;
+        (a, b, c, d)
+    }
+
+    /// Pulls `x` back onto the trust radius around `centroid`, if needed,
+    /// telling whether it did.
+    fn trust(&self, centroid: &Vector<A>, x: Vector<A>) -> (Vector<A>, bool) {
+        let max_step = match self.max_step {
+            Some(max_step) => max_step,
+            None => return (x, false),
+        };
+
+        let step = &x - centroid;
+        let len = step.norm();
+        if len <= max_step {
+            return (x, false);
+        }
+        (centroid + step * (max_step / len), true)
+    }
+
+    /// Fits a quadratic on the vertices and the edge midpoints of `simplex`,
+    /// giving its minimizer and minimum if it is convex.
+    fn fit_quadratic<F, V>(
+        &self,
+        simplex: &Simplex<A, V>,
+        objective: &mut Objective<A, F, V>,
+        best: &Pair<A, V>,
+    ) -> Option<Point<A>>
+    where
+        F: Function<A, V>,
+        V: ObjectiveValue<A::Item>,
+    {
+        let half = <A::Item as NumCast>::from(0.5).unwrap();
+        let pairs = simplex.pairs();
+        let mut samples: Vec<_> = pairs
+            .iter()
+            .map(|Pair { f, x }| Point {
+                f: f.clone(),
+                x: x.0.clone(),
+            })
+            .collect();
+        for (i, a) in pairs.iter().enumerate() {
+            for b in &pairs[i + 1..] {
+                let x = (&a.x + &b.x) * half;
+                let f = objective.call(&x, Move::Fit);
+                samples.push(Point { f, x: x.0 });
+            }
+        }
+
+        let (x, f) = surrogate::fit(&samples, &best.x)?;
+        Some(Point { f, x: x.0 })
+    }
+
+    /// Performs the reflection, expansion, contraction or shrinkage step,
+    /// telling whether the simplex shrank.
+    fn classic_step<F, V>(
+        &self,
+        simplex: &mut Simplex<A, V>,
+        objective: &mut Objective<A, F, V>,
+        (a, b, c, d): (A::Item, A::Item, A::Item, A::Item),
+    ) -> bool
+    where
+        F: Function<A, V>,
+        V: ObjectiveValue<A::Item>,
+    {
+        // Centroid
+        let centroid = simplex.centroid();
+        let mut shrunk = false;
+
+        // Best
+        let fb = simplex.best().unwrap().f.clone();
+
+        // Worst
+        let mut worst = simplex.worst().cloned().unwrap();
+
+        // Reflection point
+        let (x_reflect, pulled) = self.trust(&centroid, &centroid + (&centroid - &worst.x) * a);
+
+        // Every candidate at once, when speculative
+        let mut speculated = Vec::new();
+        if self.speculative {
+            let kinds: &[Move] = if pulled {
+                &[
+                    Move::Reflection,
+                    Move::OutsideContraction,
+                    Move::InsideContraction,
+                ]
             } else {
-                // Contraction
-                let contr = {
-                    let x = if reflect.f < worst.f {
-                        // Outside contraction
-                        &centroid + (&centroid - &worst.x) * self.b
-                    } else {
-                        // Inside contraction
-                        &centroid + (&worst.x - &centroid) * self.b
-                    };
-                    Pair::new(f(&x), x)
+                &[
+                    Move::Reflection,
+                    Move::Expansion,
+                    Move::OutsideContraction,
+                    Move::InsideContraction,
+                ]
+            };
+            let xs = simplex.batch();
+            xs.push(x_reflect.0.clone());
+            if !pulled {
+                let (x, _) = self.trust(&centroid, &centroid + (&x_reflect - &centroid) * c);
+                xs.push(x.0);
+            }
+            xs.push((&centroid + (&centroid - &worst.x) * b).0);
+            xs.push((&centroid + (&worst.x - &centroid) * b).0);
+
+            let fs = objective.call_moves(xs, kinds);
+            speculated.extend(kinds.iter().copied().zip(fs));
+        }
+        let mut call =
+            |objective: &mut Objective<A, F, V>, x: &Vector<A>, kind: Move| match speculated
+                .iter()
+                .position(|&(k, _)| k == kind)
+            {
+                Some(idx) => speculated.swap_remove(idx).1,
+                None => objective.call(x, kind),
+            };
+
+        // Reflection
+        let reflect = Pair::new(call(objective, &x_reflect, Move::Reflection), x_reflect);
+
+        // Second-worst
+        let fs = simplex.second_worst().unwrap().f.clone();
+
+        // Reflection accepted
+        if reflect.f < fs {
+            worst = reflect.clone();
+            let mut kind = Move::Reflection;
+
+            // Expansion, unless already on the trust radius
+            if reflect.f < fb && !pulled {
+                let expan = {
+                    let (x, _) = self.trust(&centroid, &centroid + (reflect.x - &centroid) * c);
+                    Pair::new(call(objective, &x, Move::Expansion), x)
                 };
 
-                // Contraction accepted
-                let min = if reflect.f < worst.f {
-                    reflect.f
+                // Expansion accepted
+                if expan.f < fb {
+                    worst = expan;
+                    kind = Move::Expansion;
+                }
+            }
+            objective.accept(kind);
+        } else {
+            // Contraction
+            let (contr, kind) = {
+                let (x, kind) = if reflect.f < worst.f {
+                    // Outside contraction
+                    let x = &centroid + (&centroid - &worst.x) * b;
+                    (x, Move::OutsideContraction)
                 } else {
-                    worst.f
+                    // Inside contraction
+                    let x = &centroid + (&worst.x - &centroid) * b;
+                    (x, Move::InsideContraction)
                 };
-                if contr.f < min {
-                    worst = contr;
-                } else {
-                    // Shrinkage
-                    simplex.shrink(&mut f, self);
+                (Pair::new(call(objective, &x, kind), x), kind)
+            };
+
+            // Contraction accepted
+            let min = if reflect.f < worst.f {
+                &reflect.f
+            } else {
+                &worst.f
+            };
+            if contr.f < *min {
+                worst = contr;
+                objective.accept(kind);
+            } else {
+                // Shrinkage
+                simplex.shrink(|xs: &[A]| objective.call_batch(xs, Move::Shrink), d);
+                objective.accept(Move::Shrink);
+                shrunk = true;
+            }
+        }
+
+        // Pull update
+        simplex.update(worst);
+        shrunk
+    }
+
+    /// Performs the step of `strategy`, telling whether the simplex shrank.
+    fn strategy_step<F, V>(
+        &self,
+        strategy: &dyn Strategy<A>,
+        simplex: &mut Simplex<A, V>,
+        objective: &mut Objective<A, F, V>,
+    ) -> bool
+    where
+        F: Function<A, V>,
+        V: ObjectiveValue<A::Item>,
+    {
+        let vertices: Vec<Point<A>> = simplex
+            .pairs()
+            .iter()
+            .map(|Pair { f, x }| Point {
+                f: f.scalar(),
+                x: x.0.clone(),
+            })
+            .collect();
+
+        let mut candidates = Vec::new();
+        let step = strategy.step(&vertices, &mut |x: &A, kind: Move| {
+            let f = objective.call(x, kind);
+            let scalar = f.scalar();
+            candidates.push((Pair::new(f, Vector(x.clone())), kind));
+            scalar
+        });
+
+        match step {
+            Step::Replace(idx) => {
+                assert!(
+                    idx < candidates.len(),
+                    "the strategy replaced the worst vertex with candidate {} of {}",
+                    idx,
+                    candidates.len()
+                );
+                let (candidate, kind) = candidates.swap_remove(idx);
+                simplex.update(candidate);
+                objective.accept(kind);
+                false
+            }
+            Step::Shrink(d) => {
+                simplex.shrink(|xs: &[A]| objective.call_batch(xs, Move::Shrink), d);
+                objective.accept(Move::Shrink);
+                true
+            }
+            Step::Keep => false,
+        }
+    }
+
+    fn iterate<F, V>(
+        &self,
+        simplex: &mut Simplex<A, V>,
+        mut objective: Objective<A, F, V>,
+    ) -> Result<A, V>
+    where
+        F: Function<A, V>,
+        V: ObjectiveValue<A::Item>,
+    {
+        let (a, b, c, d) = self.coefficients(simplex.dim());
+        let max_iter = self.max_iter.limit(simplex.dim());
+        if let Some(tol_x) = &self.tol_x_each {
+            assert_eq!(tol_x.len(), simplex.dim(), "tolerance dimension mismatch");
+        }
+
+        // Sort
+        simplex.sort_unstable();
+
+        let mut tail = VecDeque::with_capacity(TAIL_LEN);
+        let mut diameters = VecDeque::with_capacity(TAIL_LEN);
+        let mut diameter0 = simplex.diameter();
+        let mut restarts = 0;
+        let mut f_restart = None;
+        let mut shrinks = 0;
+        let mut flat = 0;
+
+        for iter in 0..max_iter {
+            objective.iter = iter;
+
+            if let Some((x, message)) = objective.panicked.take() {
+                return Err(Error::Panicked { x, message });
+            }
+
+            if let Some(max_fev) = self.max_fev {
+                if objective.fev >= max_fev {
+                    return Err(Error::MaxFev(max_fev));
                 }
             }
 
-            // Pull update
-            simplex.update(worst);
+            if let Some(max_time) = self.max_time {
+                if objective.started.elapsed() >= max_time {
+                    return Err(Error::MaxTime(max_time));
+                }
+            }
+
+            // Step
+            let shrunk = match &self.strategy {
+                Some(strategy) => self.strategy_step(strategy.as_ref(), simplex, &mut objective),
+                None => self.classic_step(simplex, &mut objective, (a, b, c, d)),
+            };
+            shrinks = if shrunk { shrinks + 1 } else { 0 };
 
             // Sort
             simplex.sort_unstable();
 
+            // Surrogate proposal
+            if self
+                .surrogate
+                .is_some_and(|period| (iter + 1) % period == 0)
+            {
+                let best = simplex.best().unwrap();
+                let fb = best.f.clone();
+                if let Some(x) = surrogate::minimizer(&objective.recent, &best.x) {
+                    let candidate = Pair::new(objective.call(&x, Move::Surrogate), x);
+                    if candidate.f < fb {
+                        simplex.update(candidate);
+                        simplex.sort_unstable();
+                        objective.accept(Move::Surrogate);
+                    }
+                }
+            }
+
+            // Re-evaluation
+            if self.reevaluate {
+                let best = simplex.best_mut().unwrap();
+                best.f = objective.call(&best.x, Move::Reevaluation);
+                simplex.sort_unstable();
+                objective.accept(Move::Reevaluation);
+            }
+
             // Termination tests
             let best = simplex.best().unwrap();
             let worst = simplex.worst().unwrap();
 
+            // Divergence test
+            if tail.len() == TAIL_LEN {
+                tail.pop_front();
+            }
+            tail.push_back(Point {
+                f: best.f.clone(),
+                x: best.x.0.clone(),
+            });
+            if diameters.len() == TAIL_LEN {
+                diameters.pop_front();
+            }
+            let diameter = simplex.diameter();
+            diameters.push_back(diameter);
+
+            // Best value falling at each iteration of the tail while the
+            // simplex keeps growing, beyond the precision of its first size
+            let two = A::Item::one() + A::Item::one();
+            let unbounded = tail.len() == TAIL_LEN
+                && tail
+                    .iter()
+                    .zip(tail.iter().skip(1))
+                    .all(|(prev, next)| next.f < prev.f)
+                && diameters
+                    .iter()
+                    .zip(diameters.iter().skip(1))
+                    .all(|(prev, next)| next >= prev)
+                && diameter >= two * diameters[0]
+                && diameter * A::Item::epsilon() > diameter0;
+
+            let diverged = unbounded
+                || best.f.scalar() == A::Item::neg_infinity()
+                || simplex.pairs().iter().any(|pair| {
+                    pair.x
+                        .iter()
+                        .any(|xi| !xi.is_finite() || xi.abs() > self.max_x)
+                });
+            if diverged {
+                return Err(Error::Diverged(tail.into()));
+            }
+
             // Domain convergence test
             let mut buf = &worst.x - &best.x;
             buf.iter_mut().for_each(|x| *x = x.abs());
-            buf.as_mut()
-                .sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
-            let test_x = *buf.as_ref().last().unwrap();
+            let converged_x = match &self.tol_x_each {
+                Some(tol_x) => buf.iter().zip(tol_x).all(|(x, tol)| x <= tol),
+                None if self.tol_x_all => simplex
+                    .pairs()
+                    .iter()
+                    .skip(1)
+                    .all(|pair| (&pair.x - &best.x).iter().all(|xi| xi.abs() <= self.tol_x)),
+                None => {
+                    buf.as_mut()
+                        .sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                    let test_x = *buf.as_ref().last().unwrap();
+                    test_x <= self.tol_x
+                }
+            };
 
             // Function value convergence test
-            let test_f = (worst.f - best.f).abs();
+            let test_f = (worst.f.scalar() - best.f.scalar()).abs();
+
+            // Flat landscape test
+            let f_scale = best.f.scalar().abs().max(A::Item::one());
+            flat = if test_f <= A::Item::epsilon() * f_scale {
+                flat + 1
+            } else {
+                0
+            };
 
             // Termination test
-            if test_f <= self.tol_f && test_x <= self.tol_x {
+            let termination = if self.flat_iter.is_some_and(|flat_iter| flat >= flat_iter) {
+                Some(Termination::FlatObjective)
+            } else if test_f <= self.tol_f && converged_x {
+                Some(Termination::Tolerance)
+            } else if self.tol_grad.is_some_and(|tol_grad| {
+                // Simplex gradient convergence test
+                simplex
+                    .gradient()
+                    .is_some_and(|grad| grad.norm() * simplex.diameter() <= tol_grad)
+            }) {
+                Some(Termination::Gradient)
+            } else if self
+                .polish
+                .is_some_and(|polish| simplex.diameter() <= polish.threshold)
+            {
+                Some(Termination::Handoff)
+            } else {
+                None
+            };
+
+            // Repeated shrink safeguard
+            let termination = match self.max_shrinks {
+                Some((limit, reaction)) if termination.is_none() && shrinks >= limit => {
+                    shrinks = 0;
+                    match reaction {
+                        ShrinkReaction::Terminate => Some(Termination::Shrinks),
+                        ShrinkReaction::Restart => {
+                            let x0 = best.x.clone();
+                            simplex.rebuild(
+                                x0,
+                                |xs: &[A]| objective.call_batch(xs, Move::Restart),
+                                self,
+                            );
+                            simplex.sort_unstable();
+                            diameter0 = simplex.diameter();
+                            diameters.clear();
+                            continue;
+                        }
+                    }
+                }
+                _ => termination,
+            };
+
+            if let Some(termination) = termination {
+                // Restart
+                if !matches!(
+                    termination,
+                    Termination::Shrinks | Termination::FlatObjective
+                ) && restarts < self.restarts
+                    && f_restart.as_ref().is_none_or(|f| best.f < *f)
+                {
+                    restarts += 1;
+                    f_restart = Some(best.f.clone());
+
+                    let x0 = best.x.clone();
+                    simplex.rebuild(x0, |xs: &[A]| objective.call_batch(xs, Move::Restart), self);
+                    simplex.sort_unstable();
+                    diameter0 = simplex.diameter();
+                    diameters.clear();
+                    continue;
+                }
+
+                let mut best = best.clone();
+                let mut phase = Phase::NelderMead;
+
+                // Quadratic fit
+                let fitted = if self.fit {
+                    self.fit_quadratic(simplex, &mut objective, &best)
+                } else {
+                    None
+                };
+
+                // Polish phase
+                if let Some(polish) = &self.polish {
+                    let refined = polish::bfgs(&mut objective, &best, simplex.diameter(), polish);
+                    if refined.f < best.f {
+                        best = refined;
+                        phase = Phase::Polish;
+                    }
+                }
+
+                if let Some((x, message)) = objective.panicked.take() {
+                    return Err(Error::Panicked { x, message });
+                }
+
                 return Ok(Output {
                     f_min: best.f,
-                    x_min: best.x.0.clone(),
+                    x_min: best.x.0,
                     iter,
+                    fev: objective.fev,
+                    timing: objective.timing(),
+                    best_ever: objective.best.unwrap(),
+                    termination,
+                    spread: simplex.spread().0,
+                    log: objective.log.unwrap_or_default(),
+                    phase,
+                    fitted,
+                    trajectory: objective.trajectory.unwrap_or_default(),
                 });
             }
         }
 
-        Err(MaxIterError(max_iter))
+        Err(Error::MaxIter {
+            limit: self.max_iter,
+            iter: max_iter,
+        })
+    }
+}
+
+impl<T: Float, const N: usize> Minimizer<[T; N]>
+where
+    [T; N]: Array<Item = T>,
+{
+    /// Minimizes the function `f` with the seed `x0`,
+    /// a dimension mismatch being a compile-time error.
+    pub fn minimize_n<F>(&self, x0: &[T; N], f: F) -> Result<[T; N]>
+    where
+        F: FnMut(&[T; N]) -> T,
+    {
+        self.minimize(x0, f)
     }
 }
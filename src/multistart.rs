@@ -0,0 +1,172 @@
+use array::Array;
+use num_traits::Float;
+
+use crate::minimizer::Minimizer;
+use crate::minimizer::Output;
+use crate::minimizer::Point;
+use crate::objective::Function;
+use crate::vector::Vector;
+
+use std::fmt::Debug;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Results of a multi-start minimization.
+pub struct MultiStart<A: Array> {
+    /// Best output over the starts, if any converged.
+    pub best: Option<Output<A>>,
+
+    /// Best point evaluated over every start, converged or not.
+    pub best_ever: Option<Point<A>>,
+
+    /// Function evaluations consumed by each start.
+    pub fev: Vec<usize>,
+
+    /// Wall-clock time consumed by each start.
+    pub time: Vec<Duration>,
+}
+
+impl<A: Array + Debug> Debug for MultiStart<A>
+where
+    A::Item: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiStart")
+            .field("best", &self.best)
+            .field("best_ever", &self.best_ever)
+            .field("fev", &self.fev)
+            .field("time", &self.time)
+            .finish()
+    }
+}
+
+impl<A: Array> Minimizer<A>
+where
+    A::Item: Float,
+{
+    /// Minimizes the function `f` from each seed of `seeds` in turn,
+    /// sharing the function evaluation and time limits between all the starts.
+    ///
+    /// No start is spawned once the remaining evaluations or time fall below
+    /// the mean consumption of the previous starts. The running start
+    /// stops at the remaining limits, its best point being kept
+    /// as `MultiStart::best_ever` even though it did not converge.
+    pub fn minimize_multistart<V, F>(&self, seeds: &[V], f: F) -> MultiStart<A>
+    where
+        V: AsRef<[A::Item]>,
+        F: FnMut(&A) -> A::Item,
+    {
+        self.multistart(seeds, f)
+    }
+
+    /// Minimizes the function `f`, evaluated point by point
+    /// or batch by batch, from each seed of `seeds` in turn.
+    pub(crate) fn multistart<V, F>(&self, seeds: &[V], mut f: F) -> MultiStart<A>
+    where
+        V: AsRef<[A::Item]>,
+        F: Function<A, A::Item>,
+    {
+        let mut best: Option<Output<A>> = None;
+        let mut best_ever: Option<Point<A>> = None;
+        let mut fev = Vec::with_capacity(seeds.len());
+        let mut time = Vec::with_capacity(seeds.len());
+        let mut used = 0;
+        let started = Instant::now();
+
+        for seed in seeds {
+            // Remaining budget
+            let remaining = self.max_fev.map(|max_fev| max_fev.saturating_sub(used));
+            if let Some(remaining) = remaining {
+                let mean = used.checked_div(fev.len()).unwrap_or(0);
+                if remaining == 0 || remaining < mean {
+                    break;
+                }
+            }
+
+            // Remaining time
+            let elapsed = started.elapsed();
+            let time_left = self
+                .max_time
+                .map(|max_time| max_time.saturating_sub(elapsed));
+            if let Some(time_left) = time_left {
+                let mean = elapsed.checked_div(time.len() as u32).unwrap_or_default();
+                if time_left.is_zero() || time_left < mean {
+                    break;
+                }
+            }
+
+            let mut local = self.clone();
+            local.max_fev = remaining;
+            local.max_time = time_left;
+
+            let mut count = 0;
+            let counted = Counted {
+                f: &mut f,
+                count: &mut count,
+                best: &mut best_ever,
+            };
+            let start = Instant::now();
+            let result = local.start(Vector::from_slice(seed.as_ref()).0, counted);
+            used += count;
+            fev.push(count);
+            time.push(start.elapsed());
+
+            if let Ok(output) = result {
+                if best.as_ref().is_none_or(|best| output.f_min < best.f_min) {
+                    best = Some(output);
+                }
+            }
+        }
+
+        MultiStart {
+            best,
+            best_ever,
+            fev,
+            time,
+        }
+    }
+}
+
+/// Function counting its evaluations and keeping the best point.
+struct Counted<'a, A: Array, F> {
+    f: &'a mut F,
+    count: &'a mut usize,
+    best: &'a mut Option<Point<A>>,
+}
+
+impl<A: Array, F> Counted<'_, A, F>
+where
+    A::Item: Float,
+{
+    fn keep(&mut self, x: &A, fx: A::Item) {
+        if self.best.as_ref().map_or(!fx.is_nan(), |best| fx < best.f) {
+            *self.best = Some(Point {
+                f: fx,
+                x: x.clone(),
+            });
+        }
+    }
+}
+
+impl<A: Array, F: Function<A, A::Item>> Function<A, A::Item> for Counted<'_, A, F>
+where
+    A::Item: Float,
+{
+    const BATCH: bool = F::BATCH;
+
+    fn value(&mut self, x: &A) -> A::Item {
+        *self.count += 1;
+        let fx = self.f.value(x);
+        self.keep(x, fx);
+        fx
+    }
+
+    fn values(&mut self, xs: &[A]) -> Vec<A::Item> {
+        *self.count += xs.len();
+        let fs = self.f.values(xs);
+        for (x, &fx) in xs.iter().zip(&fs) {
+            self.keep(x, fx);
+        }
+        fs
+    }
+}
@@ -0,0 +1,542 @@
+use array::Array;
+use num_traits::Float;
+
+use crate::minimizer::Error;
+use crate::minimizer::Point;
+
+use std::any::Any;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Kind of step that triggered a function evaluation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Move {
+    Init,
+    Reflection,
+    Expansion,
+    OutsideContraction,
+    InsideContraction,
+    Shrink,
+    Surrogate,
+    Polish,
+    Restart,
+    Reevaluation,
+    Trial,
+    Fit,
+}
+
+/// Function value, compared to order the points.
+///
+/// Floating-point values are compared as such, other values such as
+/// `(penalty, f)` pairs may order the points lexicographically.
+pub trait ObjectiveValue<T>: Clone + PartialOrd {
+    /// Gives the scalar used by the tolerance tests and the numerical steps.
+    fn scalar(&self) -> T;
+
+    /// Gives the value of infeasible points, worse than any other.
+    fn infeasible() -> Self;
+}
+
+impl<T: Float> ObjectiveValue<T> for T {
+    fn scalar(&self) -> T {
+        *self
+    }
+
+    fn infeasible() -> T {
+        T::infinity()
+    }
+}
+
+/// Function values compared on the primary one,
+/// ties being broken on the secondary one.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Lexicographic<T> {
+    pub primary: T,
+    pub secondary: T,
+}
+
+impl<T: Float> ObjectiveValue<T> for Lexicographic<T> {
+    /// Gives the primary value.
+    fn scalar(&self) -> T {
+        self.primary
+    }
+
+    fn infeasible() -> Lexicographic<T> {
+        Lexicographic {
+            primary: T::infinity(),
+            secondary: T::infinity(),
+        }
+    }
+}
+
+/// Function value carrying auxiliary user data,
+/// compared on the value only.
+#[derive(Debug, Clone)]
+pub struct Auxiliary<T, X> {
+    pub f: T,
+    pub aux: X,
+}
+
+impl<T: PartialEq, X> PartialEq for Auxiliary<T, X> {
+    fn eq(&self, other: &Auxiliary<T, X>) -> bool {
+        self.f == other.f
+    }
+}
+
+impl<T: PartialOrd, X> PartialOrd for Auxiliary<T, X> {
+    fn partial_cmp(&self, other: &Auxiliary<T, X>) -> Option<std::cmp::Ordering> {
+        self.f.partial_cmp(&other.f)
+    }
+}
+
+impl<T: Float, X: Clone + Default> ObjectiveValue<T> for Auxiliary<T, X> {
+    fn scalar(&self) -> T {
+        self.f
+    }
+
+    fn infeasible() -> Auxiliary<T, X> {
+        Auxiliary {
+            f: T::infinity(),
+            aux: X::default(),
+        }
+    }
+}
+
+/// Typed parameters, such as unit-checked quantities,
+/// converted to and from the scalars of the simplex.
+pub trait Parameters<T>: Sized {
+    /// Gives the scalar coordinates of the parameters.
+    fn to_scalars(&self) -> Vec<T>;
+
+    /// Builds the parameters from scalar coordinates.
+    fn from_scalars(x: &[T]) -> Self;
+}
+
+/// Recorded function evaluation.
+///
+/// `accepted` tells whether the point entered the Nelder-Mead simplex,
+/// the seed and restart vertices always do.
+#[derive(Debug, Clone)]
+pub struct Evaluation<A: Array, V = <A as Array>::Item> {
+    pub x: A,
+    pub f: V,
+    pub iter: usize,
+    pub kind: Move,
+    pub accepted: bool,
+}
+
+/// Best point so far, shared with other threads during a minimization.
+pub struct BestSoFar<A: Array, V = <A as Array>::Item> {
+    best: Mutex<Option<Point<A, V>>>,
+    fev: AtomicUsize,
+}
+
+impl<A: Array, V> BestSoFar<A, V> {
+    /// Creates an empty handle, to pass to `Minimizer::best_so_far`.
+    pub fn new() -> Arc<BestSoFar<A, V>> {
+        Arc::new(BestSoFar {
+            best: Mutex::new(None),
+            fev: AtomicUsize::new(0),
+        })
+    }
+
+    /// Gives the best point so far, if any.
+    pub fn get(&self) -> Option<Point<A, V>>
+    where
+        V: Clone,
+    {
+        let best = self.best.lock().unwrap_or_else(|err| err.into_inner());
+        best.clone()
+    }
+
+    /// Gives the number of function evaluations so far,
+    /// over every minimization sharing the handle.
+    pub fn fev(&self) -> usize {
+        self.fev.load(Ordering::Relaxed)
+    }
+
+    /// Stores `point` unless a better one is stored already,
+    /// as other runs may have published it.
+    pub(crate) fn offer<T>(&self, point: &Point<A, V>)
+    where
+        V: ObjectiveValue<T>,
+        T: Float,
+    {
+        let mut stored = self.best.lock().unwrap_or_else(|err| err.into_inner());
+        let better = match &*stored {
+            Some(stored) => point.f < stored.f || stored.f.scalar().is_nan(),
+            None => true,
+        };
+        if better {
+            *stored = Some(point.clone());
+        }
+    }
+
+    /// Counts one function evaluation.
+    pub(crate) fn count(&self) {
+        self.fev.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl<A: Array + Debug, V: Debug> Debug for BestSoFar<A, V> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let best = self.best.lock().unwrap_or_else(|err| err.into_inner());
+        f.debug_struct("BestSoFar")
+            .field("best", &*best)
+            .field("fev", &self.fev())
+            .finish()
+    }
+}
+
+/// What to do when the function panics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PanicPolicy {
+    /// Stops the minimization with an error.
+    Fail,
+    /// Treats the point as infeasible, with a value worse than any other.
+    Infeasible,
+}
+
+/// Wall-clock time statistics of the function evaluations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub min: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+    pub total: Duration,
+
+    /// Time spent outside of the function.
+    pub overhead: Duration,
+}
+
+/// Running evaluation time statistics.
+struct Timer {
+    started: Instant,
+    min: Duration,
+    max: Duration,
+    total: Duration,
+}
+
+/// Function evaluated one point or one batch of points at a time.
+pub(crate) trait Function<A: Array, V> {
+    /// Whether `values` evaluates a batch at once, rather than in turn.
+    const BATCH: bool = false;
+
+    fn value(&mut self, x: &A) -> V;
+
+    /// Gives the values of `xs`, in turn unless overridden.
+    fn values(&mut self, xs: &[A]) -> Vec<V> {
+        xs.iter().map(|x| self.value(x)).collect()
+    }
+}
+
+impl<A: Array, V, F: FnMut(&A) -> V> Function<A, V> for F {
+    fn value(&mut self, x: &A) -> V {
+        self(x)
+    }
+}
+
+/// Wraps the user function and keeps track of its evaluations.
+pub(crate) struct Objective<A: Array, F, V = <A as Array>::Item> {
+    f: F,
+    pub(crate) fev: usize,
+    pub(crate) iter: usize,
+
+    // Best point ever evaluated
+    pub(crate) best: Option<Point<A, V>>,
+
+    // Every evaluation, when recorded
+    pub(crate) log: Option<Vec<Evaluation<A, V>>>,
+
+    // Best point at each improvement, when tracked
+    pub(crate) trajectory: Option<Vec<Point<A, V>>>,
+
+    // Last evaluations
+    pub(crate) recent: VecDeque<Point<A, V>>,
+    recent_len: usize,
+
+    // Evaluation times, when timed
+    timer: Option<Timer>,
+
+    // Panic handling, when caught
+    catch: Option<PanicPolicy>,
+    pub(crate) panicked: Option<(A, String)>,
+
+    // Best point handle, when shared
+    shared: Option<Arc<BestSoFar<A, V>>>,
+
+    // Creation time, for the time limit
+    pub(crate) started: Instant,
+}
+
+impl<A: Array, F, V> Objective<A, F, V> {
+    pub(crate) fn new(f: F, record: bool, recent_len: usize) -> Objective<A, F, V> {
+        Objective {
+            f,
+            fev: 0,
+            iter: 0,
+            best: None,
+            log: if record { Some(Vec::new()) } else { None },
+            trajectory: None,
+            recent: VecDeque::with_capacity(recent_len),
+            recent_len,
+            timer: None,
+            catch: None,
+            panicked: None,
+            shared: None,
+            started: Instant::now(),
+        }
+    }
+
+    /// Publishes the best point to `handle` from now on.
+    pub(crate) fn sharing(mut self, handle: Arc<BestSoFar<A, V>>) -> Objective<A, F, V> {
+        self.shared = Some(handle);
+        self
+    }
+
+    /// Catches the panics of the function according to `policy`.
+    pub(crate) fn catching(mut self, policy: PanicPolicy) -> Objective<A, F, V> {
+        self.catch = Some(policy);
+        self
+    }
+
+    /// Tracks the best point at each improvement from now on.
+    pub(crate) fn tracked(mut self) -> Objective<A, F, V> {
+        self.trajectory = Some(Vec::new());
+        self
+    }
+
+    /// Times every evaluation from now on.
+    pub(crate) fn timed(mut self) -> Objective<A, F, V> {
+        self.timer = Some(Timer {
+            started: Instant::now(),
+            min: Duration::MAX,
+            max: Duration::ZERO,
+            total: Duration::ZERO,
+        });
+        self
+    }
+
+    /// Gives the evaluation time statistics, when timed.
+    pub(crate) fn timing(&self) -> Option<Timing> {
+        let timer = self.timer.as_ref()?;
+        let count = u32::try_from(self.fev).unwrap_or(u32::MAX).max(1);
+
+        Some(Timing {
+            min: timer.min.min(timer.max),
+            mean: timer.total / count,
+            max: timer.max,
+            total: timer.total,
+            overhead: timer.started.elapsed().saturating_sub(timer.total),
+        })
+    }
+
+    /// Fails once `max_fev` evaluations are spent, if any,
+    /// for the solvers checking the limit before each evaluation.
+    pub(crate) fn budget(&self, max_fev: Option<usize>) -> std::result::Result<(), Error<A, V>> {
+        match max_fev {
+            Some(max_fev) if self.fev >= max_fev => Err(Error::MaxFev(max_fev)),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn call(&mut self, x: &A, kind: Move) -> V
+    where
+        F: Function<A, V>,
+        V: ObjectiveValue<A::Item>,
+        A::Item: Float,
+    {
+        let started = self.timer.as_ref().map(|_| Instant::now());
+        let fx = match self.catch {
+            None => self.f.value(x),
+            Some(policy) => {
+                let f = &mut self.f;
+                match panic::catch_unwind(AssertUnwindSafe(|| f.value(x))) {
+                    Ok(fx) => fx,
+                    Err(payload) => {
+                        if policy == PanicPolicy::Fail && self.panicked.is_none() {
+                            self.panicked = Some((x.clone(), message(payload)));
+                        }
+                        V::infeasible()
+                    }
+                }
+            }
+        };
+        if let (Some(timer), Some(started)) = (&mut self.timer, started) {
+            let elapsed = started.elapsed();
+            timer.min = timer.min.min(elapsed);
+            timer.max = timer.max.max(elapsed);
+            timer.total += elapsed;
+        }
+
+        self.record(x, fx.clone(), kind);
+        fx
+    }
+
+    /// Evaluates the points `xs`, all triggered by a step of kind `kind`,
+    /// see `Objective::call_moves`.
+    pub(crate) fn call_batch(&mut self, xs: &[A], kind: Move) -> Vec<V>
+    where
+        F: Function<A, V>,
+        V: ObjectiveValue<A::Item>,
+        A::Item: Float,
+    {
+        self.call_moves(xs, &vec![kind; xs.len()])
+    }
+
+    /// Evaluates the points `xs`, triggered by steps of kinds `kinds`,
+    /// as one batch if the function allows it, each evaluation
+    /// being then timed as the mean of the batch.
+    ///
+    /// A panic makes every point of the batch infeasible,
+    /// the first one being reported.
+    pub(crate) fn call_moves(&mut self, xs: &[A], kinds: &[Move]) -> Vec<V>
+    where
+        F: Function<A, V>,
+        V: ObjectiveValue<A::Item>,
+        A::Item: Float,
+    {
+        if !F::BATCH || xs.is_empty() {
+            return xs
+                .iter()
+                .zip(kinds)
+                .map(|(x, &kind)| self.call(x, kind))
+                .collect();
+        }
+
+        let started = self.timer.as_ref().map(|_| Instant::now());
+        let fs = match self.catch {
+            None => self.f.values(xs),
+            Some(policy) => {
+                let f = &mut self.f;
+                match panic::catch_unwind(AssertUnwindSafe(|| f.values(xs))) {
+                    Ok(fs) => fs,
+                    Err(payload) => {
+                        if policy == PanicPolicy::Fail && self.panicked.is_none() {
+                            self.panicked = Some((xs[0].clone(), message(payload)));
+                        }
+                        vec![V::infeasible(); xs.len()]
+                    }
+                }
+            }
+        };
+        assert_eq!(fs.len(), xs.len(), "one value per point is expected");
+        if let (Some(timer), Some(started)) = (&mut self.timer, started) {
+            let elapsed = started.elapsed();
+            let mean = elapsed / u32::try_from(xs.len()).unwrap_or(u32::MAX);
+            timer.min = timer.min.min(mean);
+            timer.max = timer.max.max(mean);
+            timer.total += elapsed;
+        }
+
+        for ((x, fx), &kind) in xs.iter().zip(&fs).zip(kinds) {
+            self.record(x, fx.clone(), kind);
+        }
+        fs
+    }
+
+    /// Accounts for an evaluation made outside of the wrapper.
+    pub(crate) fn record(&mut self, x: &A, fx: V, kind: Move)
+    where
+        V: ObjectiveValue<A::Item>,
+        A::Item: Float,
+    {
+        self.note(x, fx, kind, true);
+    }
+
+    /// Accounts for the point `x` of known value `fx`, such as a seed,
+    /// as for an evaluation which is neither counted nor logged.
+    pub(crate) fn known(&mut self, x: &A, fx: V)
+    where
+        V: ObjectiveValue<A::Item>,
+        A::Item: Float,
+    {
+        self.note(x, fx, Move::Init, false);
+    }
+
+    fn note(&mut self, x: &A, fx: V, kind: Move, evaluated: bool)
+    where
+        V: ObjectiveValue<A::Item>,
+        A::Item: Float,
+    {
+        if evaluated {
+            self.fev += 1;
+        }
+
+        let improved = match &self.best {
+            Some(best) => fx < best.f || best.f.scalar().is_nan(),
+            None => true,
+        };
+        if improved {
+            let best = Point {
+                f: fx.clone(),
+                x: x.clone(),
+            };
+            if let Some(shared) = &self.shared {
+                shared.offer(&best);
+            }
+            if let Some(trajectory) = &mut self.trajectory {
+                trajectory.push(best.clone());
+            }
+            self.best = Some(best);
+        }
+
+        if let Some(shared) = self.shared.as_ref().filter(|_| evaluated) {
+            shared.count();
+        }
+
+        if self.recent_len > 0 {
+            if self.recent.len() == self.recent_len {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(Point {
+                f: fx.clone(),
+                x: x.clone(),
+            });
+        }
+
+        if let Some(log) = self.log.as_mut().filter(|_| evaluated) {
+            log.push(Evaluation {
+                x: x.clone(),
+                f: fx,
+                iter: self.iter,
+                kind,
+                accepted: matches!(kind, Move::Init | Move::Restart),
+            });
+        }
+    }
+
+    /// Marks the recorded evaluations of kind `kind`
+    /// of the current iteration as accepted.
+    pub(crate) fn accept(&mut self, kind: Move) {
+        if let Some(log) = &mut self.log {
+            for evaluation in log.iter_mut().rev() {
+                if evaluation.iter != self.iter {
+                    break;
+                }
+                if evaluation.kind == kind {
+                    evaluation.accepted = true;
+                }
+            }
+        }
+    }
+}
+
+/// Extracts the message of a panic payload.
+fn message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(message) => message.to_string(),
+            Err(_) => String::from("unknown panic"),
+        },
+    }
+}
@@ -0,0 +1,370 @@
+use array::Array;
+use num_traits::Float;
+use num_traits::Zero;
+
+use crate::bayes::BayesianOptimization;
+use crate::cma::CmaEs;
+use crate::cobyla::Cobyla;
+use crate::de::DifferentialEvolution;
+use crate::direct::Direct;
+use crate::minimizer::Error;
+use crate::minimizer::MaxIter;
+use crate::minimizer::Minimizer;
+use crate::minimizer::Output;
+use crate::minimizer::Point;
+use crate::minimizer::Result;
+use crate::objective::BestSoFar;
+use crate::pso::ParticleSwarm;
+use crate::random::RandomSearch;
+use crate::spsa::Spsa;
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Solver, with its parameters.
+pub enum Method<A: Array> {
+    NelderMead(Minimizer<A>),
+    Cobyla(Cobyla<A>),
+    CmaEs(CmaEs<A>),
+    Spsa(Spsa<A>),
+    DifferentialEvolution(DifferentialEvolution<A>),
+    ParticleSwarm(ParticleSwarm<A>),
+    Direct(Direct<A>),
+    RandomSearch(RandomSearch<A>),
+    BayesianOptimization(BayesianOptimization<A>),
+}
+
+impl<A: Array + Debug> Debug for Method<A>
+where
+    A::Item: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Method::NelderMead(solver) => f.debug_tuple("NelderMead").field(solver).finish(),
+            Method::Cobyla(solver) => f.debug_tuple("Cobyla").field(solver).finish(),
+            Method::CmaEs(solver) => f.debug_tuple("CmaEs").field(solver).finish(),
+            Method::Spsa(solver) => f.debug_tuple("Spsa").field(solver).finish(),
+            Method::DifferentialEvolution(solver) => f
+                .debug_tuple("DifferentialEvolution")
+                .field(solver)
+                .finish(),
+            Method::ParticleSwarm(solver) => f.debug_tuple("ParticleSwarm").field(solver).finish(),
+            Method::Direct(solver) => f.debug_tuple("Direct").field(solver).finish(),
+            Method::RandomSearch(solver) => f.debug_tuple("RandomSearch").field(solver).finish(),
+            Method::BayesianOptimization(solver) => {
+                f.debug_tuple("BayesianOptimization").field(solver).finish()
+            }
+        }
+    }
+}
+
+impl<A: Array> Method<A> {
+    /// Tells whether the solver searches the whole box bounds,
+    /// rather than around the seed.
+    pub fn is_global(&self) -> bool {
+        !matches!(
+            self,
+            Method::NelderMead(_) | Method::Cobyla(_) | Method::CmaEs(_) | Method::Spsa(_)
+        )
+    }
+}
+
+impl<A: Array> From<Minimizer<A>> for Method<A> {
+    fn from(solver: Minimizer<A>) -> Method<A> {
+        Method::NelderMead(solver)
+    }
+}
+
+impl<A: Array> From<Cobyla<A>> for Method<A> {
+    fn from(solver: Cobyla<A>) -> Method<A> {
+        Method::Cobyla(solver)
+    }
+}
+
+impl<A: Array> From<CmaEs<A>> for Method<A> {
+    fn from(solver: CmaEs<A>) -> Method<A> {
+        Method::CmaEs(solver)
+    }
+}
+
+impl<A: Array> From<Spsa<A>> for Method<A> {
+    fn from(solver: Spsa<A>) -> Method<A> {
+        Method::Spsa(solver)
+    }
+}
+
+impl<A: Array> From<DifferentialEvolution<A>> for Method<A> {
+    fn from(solver: DifferentialEvolution<A>) -> Method<A> {
+        Method::DifferentialEvolution(solver)
+    }
+}
+
+impl<A: Array> From<ParticleSwarm<A>> for Method<A> {
+    fn from(solver: ParticleSwarm<A>) -> Method<A> {
+        Method::ParticleSwarm(solver)
+    }
+}
+
+impl<A: Array> From<Direct<A>> for Method<A> {
+    fn from(solver: Direct<A>) -> Method<A> {
+        Method::Direct(solver)
+    }
+}
+
+impl<A: Array> From<RandomSearch<A>> for Method<A> {
+    fn from(solver: RandomSearch<A>) -> Method<A> {
+        Method::RandomSearch(solver)
+    }
+}
+
+impl<A: Array> From<BayesianOptimization<A>> for Method<A> {
+    fn from(solver: BayesianOptimization<A>) -> Method<A> {
+        Method::BayesianOptimization(solver)
+    }
+}
+
+/// Single entry point to every solver, so that the method
+/// can be picked at run time.
+pub struct Optimizer<A: Array> {
+    method: Method<A>,
+    bounds: Option<Vec<(A::Item, A::Item)>>,
+    best_so_far: Option<Arc<BestSoFar<A>>>,
+}
+
+impl<A: Array + Debug> Debug for Optimizer<A>
+where
+    A::Item: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Optimizer")
+            .field("method", &self.method)
+            .field("bounds", &self.bounds)
+            .field("best_so_far", &self.best_so_far)
+            .finish()
+    }
+}
+
+impl<A: Array> Optimizer<A>
+where
+    A::Item: Float,
+{
+    /// Creates an optimizer running `method`, such as `CmaEs::default()`.
+    pub fn new<M: Into<Method<A>>>(method: M) -> Optimizer<A> {
+        Optimizer {
+            method: method.into(),
+            bounds: None,
+            best_so_far: None,
+        }
+    }
+
+    /// Sets the box bounds, given as `(lower, upper)` pairs.
+    ///
+    /// The global methods search them, COBYLA takes them as constraints
+    /// and the other local methods evaluate the function at the points
+    /// projected on them, adding the squared distance to them.
+    pub fn bounds(mut self, bounds: &[(A::Item, A::Item)]) -> Optimizer<A> {
+        self.bounds = Some(bounds.to_vec());
+        self
+    }
+
+    /// Sets the evaluation limit of the method, the initial samples
+    /// of Bayesian optimization and the local refinement of the global
+    /// methods included.
+    ///
+    /// Random search and Bayesian optimization end normally there,
+    /// the other methods fail with `Error::MaxFev`.
+    pub fn max_fev(mut self, max_fev: usize) -> Optimizer<A> {
+        self.method = match self.method {
+            Method::NelderMead(solver) => Method::NelderMead(solver.max_fev(max_fev)),
+            Method::Cobyla(solver) => Method::Cobyla(solver.max_fev(max_fev)),
+            Method::CmaEs(solver) => Method::CmaEs(solver.max_fev(max_fev)),
+            Method::Spsa(solver) => Method::Spsa(solver.max_fev(max_fev)),
+            Method::DifferentialEvolution(solver) => {
+                Method::DifferentialEvolution(solver.max_fev(max_fev))
+            }
+            Method::ParticleSwarm(solver) => Method::ParticleSwarm(solver.max_fev(max_fev)),
+            Method::Direct(solver) => Method::Direct(solver.max_fev(max_fev)),
+            Method::RandomSearch(solver) => Method::RandomSearch(solver.max_fev(max_fev)),
+            Method::BayesianOptimization(solver) => Method::BayesianOptimization(
+                solver.max_iter(MaxIter::Absolute(max_fev)).max_fev(max_fev),
+            ),
+        };
+        self
+    }
+
+    /// Publishes the best point to `handle` as the search goes,
+    /// for other threads to poll, whatever the method,
+    /// see `Minimizer::best_so_far`.
+    ///
+    /// Only the points within the bounds are published,
+    /// but every evaluation is counted.
+    pub fn best_so_far(mut self, handle: Arc<BestSoFar<A>>) -> Optimizer<A> {
+        self.best_so_far = Some(handle);
+        self
+    }
+
+    /// Gives the optimizer with the panic catching of its Nelder-Mead
+    /// minimizer, or of its local refinement, turned off.
+    pub(crate) fn uncaught(mut self) -> Optimizer<A> {
+        let minimizer = match &mut self.method {
+            Method::NelderMead(solver) => Some(solver),
+            Method::DifferentialEvolution(solver) => solver.local.as_mut(),
+            Method::ParticleSwarm(solver) => solver.local.as_mut(),
+            Method::Direct(solver) => solver.local.as_mut(),
+            Method::RandomSearch(solver) => solver.local.as_mut(),
+            _ => None,
+        };
+        if let Some(minimizer) = minimizer {
+            minimizer.catch_panics = None;
+        }
+        self
+    }
+
+    /// Gives the method.
+    pub fn method(&self) -> &Method<A> {
+        &self.method
+    }
+
+    /// Minimizes the function `f` with the seed `x0`.
+    ///
+    /// The global methods ignore the seed and require the bounds,
+    /// failing with `Error::MissingBounds` otherwise.
+    pub fn minimize<F>(&self, x0: &[A::Item], f: F) -> Result<A>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        let bounds = self.bounds.as_deref();
+        let global = || bounds.ok_or(Error::MissingBounds);
+        let mut f = published(self.best_so_far.as_deref(), bounds, f);
+
+        match &self.method {
+            Method::NelderMead(solver) => solver
+                .minimize(x0, projected(bounds, &mut f))
+                .map(|output| project_output(bounds, output, f)),
+            Method::CmaEs(solver) => solver
+                .minimize(x0, projected(bounds, &mut f))
+                .map(|output| project_output(bounds, output, f)),
+            Method::Spsa(solver) => solver
+                .minimize(x0, projected(bounds, &mut f))
+                .map(|output| project_output(bounds, output, f)),
+            Method::Cobyla(solver) => solver.minimize(x0, f, |x: &A| {
+                bounds
+                    .unwrap_or_default()
+                    .iter()
+                    .zip(x.as_ref())
+                    .flat_map(|(&(lower, upper), &xi)| [xi - lower, upper - xi])
+                    .collect()
+            }),
+            Method::DifferentialEvolution(solver) => solver.minimize(global()?, f),
+            Method::ParticleSwarm(solver) => solver.minimize(global()?, f),
+            Method::Direct(solver) => solver.minimize(global()?, f),
+            Method::RandomSearch(solver) => solver.minimize(global()?, f),
+            Method::BayesianOptimization(solver) => solver.minimize(global()?, f),
+        }
+    }
+}
+
+/// Publishes the points of `f` within the bounds to `handle`, if any.
+fn published<'a, A, F>(
+    handle: Option<&'a BestSoFar<A>>,
+    bounds: Option<&'a [(A::Item, A::Item)]>,
+    mut f: F,
+) -> impl FnMut(&A) -> A::Item + 'a
+where
+    A: Array + 'a,
+    A::Item: Float,
+    F: FnMut(&A) -> A::Item + 'a,
+{
+    move |x: &A| {
+        let fx = f(x);
+        if let Some(handle) = handle {
+            let inside = bounds.is_none_or(|bounds| {
+                x.as_ref()
+                    .iter()
+                    .zip(bounds)
+                    .all(|(&xi, &(lower, upper))| lower <= xi && xi <= upper)
+            });
+            if inside {
+                handle.offer(&Point {
+                    f: fx,
+                    x: x.clone(),
+                });
+            }
+            handle.count();
+        }
+        fx
+    }
+}
+
+/// Evaluates `f` at the points projected on the bounds, if any,
+/// the squared distance to them keeping the minimum inside.
+fn projected<'a, A, F>(
+    bounds: Option<&'a [(A::Item, A::Item)]>,
+    mut f: F,
+) -> impl FnMut(&A) -> A::Item + 'a
+where
+    A: Array + 'a,
+    A::Item: Float,
+    F: FnMut(&A) -> A::Item + 'a,
+{
+    move |x: &A| match bounds {
+        Some(bounds) => {
+            let projection = project(bounds, x);
+            let excess = x
+                .as_ref()
+                .iter()
+                .zip(projection.as_ref())
+                .fold(A::Item::zero(), |acc, (&xi, &pi)| acc + (xi - pi).powi(2));
+            f(&projection) + excess
+        }
+        None => f(x),
+    }
+}
+
+/// Projects the output points on the bounds, if any.
+///
+/// The values of the points moved by the projection, which hold
+/// the squared distance to the bounds, are evaluated again with `f`.
+fn project_output<A: Array, F>(
+    bounds: Option<&[(A::Item, A::Item)]>,
+    mut output: Output<A>,
+    mut f: F,
+) -> Output<A>
+where
+    A::Item: Float,
+    F: FnMut(&A) -> A::Item,
+{
+    let bounds = match bounds {
+        Some(bounds) => bounds,
+        None => return output,
+    };
+
+    let x_min = project(bounds, &output.x_min);
+    if x_min.as_ref() != output.x_min.as_ref() {
+        output.f_min = f(&x_min);
+        output.fev += 1;
+    }
+    output.x_min = x_min;
+
+    let x = project(bounds, &output.best_ever.x);
+    if x.as_ref() == output.x_min.as_ref() {
+        output.best_ever.f = output.f_min;
+    } else if x.as_ref() != output.best_ever.x.as_ref() {
+        output.best_ever.f = f(&x);
+        output.fev += 1;
+    }
+    output.best_ever.x = x;
+    output
+}
+
+/// Projects `x` on the bounds.
+fn project<A: Array>(bounds: &[(A::Item, A::Item)], x: &A) -> A
+where
+    A::Item: Float,
+{
+    let mut x = x.clone();
+    for (xi, &(lower, upper)) in x.as_mut().iter_mut().zip(bounds) {
+        *xi = xi.max(lower).min(upper);
+    }
+    x
+}
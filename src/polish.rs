@@ -0,0 +1,175 @@
+use array::Array;
+use num_traits::Float;
+use num_traits::NumCast;
+use num_traits::One;
+use num_traits::Zero;
+
+use crate::objective::Function;
+use crate::objective::Move;
+use crate::objective::Objective;
+use crate::objective::ObjectiveValue;
+use crate::simplex::Pair;
+use crate::vector::Vector;
+
+/// Quasi-Newton refinement of the Nelder-Mead estimation.
+///
+/// Once the simplex is small, a BFGS method with finite-difference gradients
+/// takes over from the best vertex.
+#[derive(Debug, Clone, Copy)]
+pub struct Polish<T> {
+    /// Simplex diameter below which the refinement takes over.
+    pub threshold: T,
+
+    /// Relative step of the finite differences.
+    pub fd_step: T,
+
+    /// Maximal number of quasi-Newton iterations.
+    pub max_iter: usize,
+}
+
+impl<T: Float> Default for Polish<T> {
+    fn default() -> Polish<T> {
+        Polish {
+            threshold: <T as NumCast>::from(1e-2).unwrap(),
+            fd_step: T::epsilon().cbrt(),
+            max_iter: 100,
+        }
+    }
+}
+
+/// Central finite-difference gradient.
+fn gradient<A, F, V>(
+    objective: &mut Objective<A, F, V>,
+    x: &Vector<A>,
+    fd_step: A::Item,
+) -> Vector<A>
+where
+    A: Array,
+    A::Item: Float,
+    F: Function<A, V>,
+    V: ObjectiveValue<A::Item>,
+{
+    let two = <A::Item as NumCast>::from(2.0).unwrap();
+    let mut buf = x.clone();
+    let mut grad = Vector::zeros(x.iter().len());
+    for (idx, &xi) in x.iter().enumerate() {
+        let h = fd_step * xi.abs().max(A::Item::one());
+        buf[idx] = xi + h;
+        let forward = objective.call(&buf, Move::Polish).scalar();
+        buf[idx] = xi - h;
+        let backward = objective.call(&buf, Move::Polish).scalar();
+        buf[idx] = xi;
+        grad[idx] = (forward - backward) / (two * h);
+    }
+    grad
+}
+
+/// Refines `start` with a BFGS method and a backtracking line search.
+///
+/// The first step is scaled to `scale`, typically the simplex diameter.
+/// The line search works on the scalar of the function values.
+pub(crate) fn bfgs<A, F, V>(
+    objective: &mut Objective<A, F, V>,
+    start: &Pair<A, V>,
+    scale: A::Item,
+    polish: &Polish<A::Item>,
+) -> Pair<A, V>
+where
+    A: Array,
+    A::Item: Float,
+    F: Function<A, V>,
+    V: ObjectiveValue<A::Item>,
+{
+    let dim = start.x.iter().len();
+    let half = <A::Item as NumCast>::from(0.5).unwrap();
+    let armijo = <A::Item as NumCast>::from(1e-4).unwrap();
+
+    let mut x = start.x.clone();
+    let mut value = start.f.clone();
+    let mut fx = value.scalar();
+    let mut grad = gradient(objective, &x, polish.fd_step);
+
+    // Inverse Hessian estimation
+    let norm = grad.norm();
+    if norm.is_zero() || !norm.is_finite() {
+        return start.clone();
+    }
+    let mut inv_hess = vec![A::Item::zero(); dim * dim];
+    for idx in 0..dim {
+        inv_hess[idx * dim + idx] = scale / norm;
+    }
+
+    for _ in 0..polish.max_iter {
+        // Search direction
+        let dir: Vector<A> = (0..dim)
+            .map(|i| {
+                -(0..dim).fold(A::Item::zero(), |acc, j| {
+                    inv_hess[i * dim + j].mul_add(grad[j], acc)
+                })
+            })
+            .collect();
+        let slope = grad.dot(&dir);
+        if slope.is_nan() || slope >= A::Item::zero() {
+            break;
+        }
+
+        // Backtracking line search
+        let mut t = A::Item::one();
+        let next = loop {
+            let mut y = x.clone();
+            y.scaled_add(t, &dir);
+            let value = objective.call(&y, Move::Polish);
+            if value.scalar() <= fx + armijo * t * slope {
+                break Some(Pair::new(value, y));
+            }
+            t = t * half;
+            if t < A::Item::epsilon() {
+                break None;
+            }
+        };
+        let next = match next {
+            Some(next) => next,
+            None => break,
+        };
+
+        let next_grad = gradient(objective, &next.x, polish.fd_step);
+        let s = &next.x - &x;
+        let y = &next_grad - &grad;
+        let sy = s.dot(&y);
+
+        // BFGS update
+        if sy > A::Item::zero() {
+            let rho = sy.recip();
+            let hy: Vec<_> = (0..dim)
+                .map(|i| {
+                    (0..dim).fold(A::Item::zero(), |acc, j| {
+                        inv_hess[i * dim + j].mul_add(y[j], acc)
+                    })
+                })
+                .collect();
+            let yhy = y
+                .iter()
+                .zip(&hy)
+                .fold(A::Item::zero(), |acc, (&a, &b)| a.mul_add(b, acc));
+            let coef = (A::Item::one() + rho * yhy) * rho;
+            for i in 0..dim {
+                for j in 0..dim {
+                    inv_hess[i * dim + j] = inv_hess[i * dim + j] + coef * s[i] * s[j]
+                        - rho * (hy[i] * s[j] + s[i] * hy[j]);
+                }
+            }
+        }
+
+        let improvement = fx - next.f.scalar();
+        x = next.x;
+        fx = next.f.scalar();
+        value = next.f;
+        grad = next_grad;
+
+        if improvement <= A::Item::epsilon() * fx.abs() {
+            break;
+        }
+    }
+
+    Pair::new(value, x)
+}
@@ -0,0 +1,155 @@
+use array::Array;
+use num_traits::Float;
+
+use crate::minimizer::Error;
+use crate::minimizer::Result;
+use crate::optimizer::Optimizer;
+
+use std::fmt::Debug;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::thread;
+
+/// Unwinding payload stopping a cancelled solver.
+struct Cancelled;
+
+/// Solvers racing on the same function, each in its own thread,
+/// with a shared function evaluation limit.
+///
+/// The cancellation unwinds out of the function, so the panic catching
+/// of the solvers is turned off, see `Minimizer::catch_panics`.
+pub struct Portfolio<A: Array> {
+    // Solvers parameter
+    solvers: Vec<Optimizer<A>>,
+
+    // Shared function evaluations parameter
+    max_fev: Option<usize>,
+
+    // Cancellation on the first result parameter
+    first: bool,
+}
+
+impl<A: Array + Debug> Debug for Portfolio<A>
+where
+    A::Item: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Portfolio")
+            .field("solvers", &self.solvers)
+            .field("max_fev", &self.max_fev)
+            .field("first", &self.first)
+            .finish()
+    }
+}
+
+impl<A: Array> Default for Portfolio<A> {
+    fn default() -> Portfolio<A> {
+        Portfolio {
+            solvers: Vec::new(),
+            max_fev: None,
+            first: false,
+        }
+    }
+}
+
+impl<A: Array> Portfolio<A>
+where
+    A: Send + Sync,
+    A::Item: Float + Send + Sync,
+{
+    /// Adds a solver to the race, turning off its panic catching.
+    pub fn solver(mut self, solver: Optimizer<A>) -> Portfolio<A> {
+        self.solvers.push(solver.uncaught());
+        self
+    }
+
+    /// Sets the function evaluation limit, shared between the solvers,
+    /// every running solver being cancelled once it is reached.
+    pub fn max_fev(mut self, max_fev: usize) -> Portfolio<A> {
+        self.max_fev = Some(max_fev);
+        self
+    }
+
+    /// Cancels the other solvers as soon as one converges,
+    /// rather than waiting for all of them.
+    pub fn first(mut self, first: bool) -> Portfolio<A> {
+        self.first = first;
+        self
+    }
+
+    /// Minimizes the function `f` with the seed `x0`,
+    /// giving the best output of the solvers which converged.
+    ///
+    /// The output counts the evaluations of its solver only.
+    /// When none converged, the first error is given,
+    /// or `Error::MaxFev` once every solver was cancelled.
+    pub fn minimize<F>(&self, x0: &[A::Item], f: F) -> Result<A>
+    where
+        F: Fn(&A) -> A::Item + Sync,
+    {
+        let fev = AtomicUsize::new(0);
+        let done = AtomicBool::new(false);
+
+        let results: Vec<Option<Result<A>>> = thread::scope(|scope| {
+            let handles: Vec<_> = self
+                .solvers
+                .iter()
+                .map(|solver| {
+                    let (f, fev, done) = (&f, &fev, &done);
+                    scope.spawn(move || {
+                        let counted = |x: &A| {
+                            let count = fev.fetch_add(1, Ordering::Relaxed) + 1;
+                            let exhausted = self.max_fev.is_some_and(|max_fev| count > max_fev);
+                            if exhausted || done.load(Ordering::Relaxed) {
+                                panic::resume_unwind(Box::new(Cancelled));
+                            }
+                            f(x)
+                        };
+
+                        match panic::catch_unwind(AssertUnwindSafe(|| solver.minimize(x0, counted)))
+                        {
+                            Ok(result) => {
+                                if self.first && result.is_ok() {
+                                    done.store(true, Ordering::Relaxed);
+                                }
+                                Some(result)
+                            }
+                            Err(payload) if payload.is::<Cancelled>() => None,
+                            Err(payload) => panic::resume_unwind(payload),
+                        }
+                    })
+                })
+                .collect();
+
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|err| panic::resume_unwind(err))
+                })
+                .collect()
+        });
+
+        let mut best: Option<Result<A>> = None;
+        for result in results.into_iter().flatten() {
+            let better = match (&best, &result) {
+                (None, _) => true,
+                (Some(Ok(best)), Ok(output)) => output.f_min < best.f_min,
+                (Some(Err(_)), Ok(_)) => true,
+                (Some(_), Err(_)) => false,
+            };
+            if better {
+                best = Some(result);
+            }
+        }
+
+        best.unwrap_or_else(|| {
+            let max_fev = self.max_fev.unwrap_or_else(|| fev.load(Ordering::Relaxed));
+            Err(Error::MaxFev(max_fev))
+        })
+    }
+}
@@ -0,0 +1,105 @@
+use array::Array;
+use num_traits::Float;
+use num_traits::NumCast;
+use num_traits::One;
+
+use crate::minimizer::Error;
+use crate::minimizer::Minimizer;
+use crate::minimizer::Output;
+use crate::minimizer::Result;
+
+impl<A: Array> Minimizer<A>
+where
+    A::Item: Float,
+{
+    /// Minimizes the function `f` with the seed `x0`, first in the cheaper
+    /// precision of `B`, such as `f32`, with `f_low`, then refining
+    /// the estimation in the precision of `A`.
+    ///
+    /// The cheap stage runs the parameters cast to `B`, with tolerances
+    /// no finer than the square root of its machine epsilon. Should it fail,
+    /// the minimization starts over in `A` from the seed.
+    pub fn minimize_staged<B, G, F>(&self, x0: &[A::Item], mut f_low: G, f: F) -> Result<A>
+    where
+        B: Array,
+        B::Item: Float,
+        G: FnMut(&B) -> B::Item,
+        F: FnMut(&A) -> A::Item,
+    {
+        let mut low = self.cast::<B>();
+        let resolution = B::Item::epsilon().sqrt();
+        low.tol_f = low.tol_f.max(resolution);
+        low.tol_x = low.tol_x.max(resolution);
+
+        let seed: Vec<B::Item> = x0
+            .iter()
+            .map(|&x| <B::Item as NumCast>::from(x).unwrap())
+            .collect();
+        let mut count = 0;
+        let coarse = low.minimize(&seed, |x: &B| {
+            count += 1;
+            f_low(x)
+        });
+
+        match coarse {
+            Ok(coarse) => self.refine(&coarse, f),
+            Err(_) => self.after(count, 0, |minimizer| minimizer.minimize(x0, f)),
+        }
+    }
+
+    /// Refines the output of a minimization in a lower precision,
+    /// for instance the one of `minimize_staged` in a higher one.
+    ///
+    /// The simplex is rebuilt around the estimation, its edges being
+    /// the coarse spread, no shorter than the coarse precision allows.
+    /// The output counts the iterations and evaluations of both stages,
+    /// which share the function evaluation limit.
+    pub fn refine<B, F>(&self, coarse: &Output<B>, f: F) -> Result<A>
+    where
+        B: Array,
+        B::Item: Float,
+        F: FnMut(&A) -> A::Item,
+    {
+        let cast = |x: B::Item| <A::Item as NumCast>::from(x).unwrap();
+        let resolution = B::Item::epsilon().sqrt();
+
+        let x0: Vec<A::Item> = coarse.x_min.as_ref().iter().map(|&x| cast(x)).collect();
+        let mut vertices = vec![x0.clone()];
+        for (idx, (&xi, &spread)) in coarse
+            .x_min
+            .as_ref()
+            .iter()
+            .zip(coarse.spread.as_ref())
+            .enumerate()
+        {
+            let edge = spread.abs().max(resolution * xi.abs().max(B::Item::one()));
+            let mut vertex = x0.clone();
+            vertex[idx] = vertex[idx] + cast(edge);
+            vertices.push(vertex);
+        }
+
+        self.after(coarse.fev, coarse.iter, |minimizer| {
+            minimizer.minimize_simplex(&vertices, f)
+        })
+    }
+
+    /// Runs a stage after `fev` evaluations and `iter` iterations,
+    /// the evaluation limit being reduced accordingly.
+    pub(crate) fn after<R>(&self, fev: usize, iter: usize, run: R) -> Result<A>
+    where
+        R: FnOnce(&Minimizer<A>) -> Result<A>,
+    {
+        let mut minimizer = self.clone();
+        minimizer.max_fev = self.max_fev.map(|max_fev| max_fev.saturating_sub(fev));
+
+        match run(&minimizer) {
+            Ok(mut output) => {
+                output.iter += iter;
+                output.fev += fev;
+                Ok(output)
+            }
+            Err(Error::MaxFev(_)) => Err(Error::MaxFev(self.max_fev.unwrap_or(fev))),
+            Err(err) => Err(err),
+        }
+    }
+}
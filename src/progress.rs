@@ -0,0 +1,43 @@
+use array::Array;
+use indicatif::ProgressBar;
+use num_traits::Float;
+
+use crate::minimizer::Minimizer;
+
+use std::fmt::Display;
+
+/// Wraps the function `f` to drive the progress bar `bar`,
+/// one step per evaluation, the best value so far being the message.
+///
+/// For instance `minimizer.minimize(&x0, progress(&minimizer.progress_bar(), f))`.
+pub fn progress<'a, A, F>(bar: &'a ProgressBar, mut f: F) -> impl FnMut(&A) -> A::Item + 'a
+where
+    A: Array + 'a,
+    A::Item: Float + Display,
+    F: FnMut(&A) -> A::Item + 'a,
+{
+    let mut best = A::Item::infinity();
+    move |x: &A| {
+        let fx = f(x);
+        bar.inc(1);
+        if fx < best {
+            best = fx;
+            bar.set_message(format!("f = {}", best));
+        }
+        fx
+    }
+}
+
+impl<A: Array> Minimizer<A>
+where
+    A::Item: Float,
+{
+    /// Creates a progress bar over the function evaluation limit,
+    /// a spinner without a limit, to drive with `progress`.
+    pub fn progress_bar(&self) -> ProgressBar {
+        match self.max_fev {
+            Some(max_fev) => ProgressBar::new(max_fev as u64),
+            None => ProgressBar::new_spinner(),
+        }
+    }
+}
@@ -0,0 +1,310 @@
+use array::Array;
+use num_traits::Float;
+use num_traits::NumCast;
+use num_traits::Zero;
+
+use crate::global::refine;
+use crate::global::spread;
+use crate::global::Phase;
+use crate::minimizer::tolerance;
+use crate::minimizer::Error;
+use crate::minimizer::MaxIter;
+use crate::minimizer::Minimizer;
+use crate::minimizer::Output;
+use crate::minimizer::Result;
+use crate::minimizer::Termination;
+use crate::objective::BestSoFar;
+use crate::objective::Move;
+use crate::objective::Objective;
+use crate::rng::Rng;
+use crate::simplex::Pair;
+use crate::vector::Vector;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use std::sync::Arc;
+
+/// A structure that holds all the particle swarm parameters.
+///
+/// It searches the global minimum within box bounds,
+/// each particle being pulled towards its own best point and the swarm best.
+#[derive(Debug)]
+pub struct ParticleSwarm<A: Array> {
+    // Swarm size parameter, per dimension
+    swarm_size: usize,
+
+    // Inertia weight parameter
+    inertia: A::Item,
+
+    // Cognitive (own best) acceleration parameter
+    cognitive: A::Item,
+
+    // Social (swarm best) acceleration parameter
+    social: A::Item,
+
+    // Tolerance (function) parameter
+    tol_f: A::Item,
+
+    // Tolerance (point) parameter
+    tol_x: A::Item,
+
+    // Generations parameter
+    max_iter: MaxIter,
+
+    // Function evaluations parameter
+    max_fev: Option<usize>,
+
+    // Random generator parameter
+    seed: u64,
+
+    // Local refinement parameter
+    pub(crate) local: Option<Minimizer<A>>,
+
+    // Shared best point parameter
+    best_so_far: Option<Arc<BestSoFar<A>>>,
+}
+
+impl<A: Array> Default for ParticleSwarm<A>
+where
+    A::Item: Float,
+{
+    fn default() -> ParticleSwarm<A> {
+        ParticleSwarm {
+            swarm_size: 10,
+            inertia: <A::Item as NumCast>::from(0.7298).unwrap(),
+            cognitive: <A::Item as NumCast>::from(1.49618).unwrap(),
+            social: <A::Item as NumCast>::from(1.49618).unwrap(),
+            tol_f: tolerance(1e-4),
+            tol_x: tolerance(1e-4),
+            max_iter: MaxIter::Absolute(1000),
+            max_fev: None,
+            seed: 0,
+            local: None,
+            best_so_far: None,
+        }
+    }
+}
+
+impl<A: Array> ParticleSwarm<A>
+where
+    A::Item: Float,
+{
+    /// Sets the swarm size, per dimension.
+    pub fn swarm_size(mut self, swarm_size: usize) -> ParticleSwarm<A> {
+        self.swarm_size = swarm_size;
+        self
+    }
+
+    /// Sets the inertia weight, usually in `[0.4, 0.9]`.
+    pub fn inertia(mut self, inertia: A::Item) -> ParticleSwarm<A> {
+        self.inertia = inertia;
+        self
+    }
+
+    /// Sets the acceleration towards the particle best.
+    pub fn cognitive(mut self, cognitive: A::Item) -> ParticleSwarm<A> {
+        self.cognitive = cognitive;
+        self
+    }
+
+    /// Sets the acceleration towards the swarm best.
+    pub fn social(mut self, social: A::Item) -> ParticleSwarm<A> {
+        self.social = social;
+        self
+    }
+
+    /// Sets the generation limit.
+    pub fn max_iter(mut self, max_iter: MaxIter) -> ParticleSwarm<A> {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Sets the function evaluation limit, the local refinement included.
+    pub fn max_fev(mut self, max_fev: usize) -> ParticleSwarm<A> {
+        self.max_fev = Some(max_fev);
+        self
+    }
+
+    /// Sets the seed of the random generator.
+    pub fn seed(mut self, seed: u64) -> ParticleSwarm<A> {
+        self.seed = seed;
+        self
+    }
+
+    /// Refines the swarm best with the Nelder-Mead `minimizer`.
+    pub fn local(mut self, minimizer: Minimizer<A>) -> ParticleSwarm<A> {
+        self.local = Some(minimizer);
+        self
+    }
+
+    /// Publishes the best point to `handle` as the search goes,
+    /// for other threads to poll, see `Minimizer::best_so_far`.
+    pub fn best_so_far(mut self, handle: Arc<BestSoFar<A>>) -> ParticleSwarm<A> {
+        self.best_so_far = Some(handle);
+        self
+    }
+
+    /// Minimizes the function `f` within the box `bounds`,
+    /// given as `(lower, upper)` pairs.
+    pub fn minimize<F>(&self, bounds: &[(A::Item, A::Item)], mut f: F) -> Result<A>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        let mut objective = self.shared(Objective::new(&mut f, false, 0));
+        let output = self.run(bounds, &mut objective, |objective, xs, kind| {
+            xs.iter().map(|x| objective.call(x, kind)).collect()
+        })?;
+
+        Ok(refine(
+            self.local.as_ref(),
+            self.best_so_far.as_ref(),
+            self.max_fev,
+            output,
+            f,
+        ))
+    }
+
+    /// Minimizes the function `f` within the box `bounds`,
+    /// evaluating the particles of a generation in parallel.
+    #[cfg(feature = "rayon")]
+    pub fn minimize_par<F>(&self, bounds: &[(A::Item, A::Item)], f: F) -> Result<A>
+    where
+        A: Send + Sync,
+        A::Item: Send + Sync,
+        F: Fn(&A) -> A::Item + Sync,
+    {
+        let mut objective = self.shared(Objective::new((), false, 0));
+        let output = self.run(bounds, &mut objective, |objective, xs, kind| {
+            let fs: Vec<A::Item> = xs.par_iter().map(|x| f(x)).collect();
+            for (x, &fx) in xs.iter().zip(&fs) {
+                objective.record(x, fx, kind);
+            }
+            fs
+        })?;
+
+        Ok(refine(
+            self.local.as_ref(),
+            self.best_so_far.as_ref(),
+            self.max_fev,
+            output,
+            &f,
+        ))
+    }
+
+    /// Publishes the best point of `objective` to the shared handle, if any.
+    fn shared<F>(&self, objective: Objective<A, F>) -> Objective<A, F> {
+        match &self.best_so_far {
+            Some(handle) => objective.sharing(handle.clone()),
+            None => objective,
+        }
+    }
+
+    /// Runs the swarm, `evaluate` giving the values of a whole generation.
+    fn run<F, E>(
+        &self,
+        bounds: &[(A::Item, A::Item)],
+        objective: &mut Objective<A, F>,
+        mut evaluate: E,
+    ) -> Result<A>
+    where
+        E: FnMut(&mut Objective<A, F>, &[Vector<A>], Move) -> Vec<A::Item>,
+    {
+        let dim = bounds.len();
+        let len = (self.swarm_size * dim).max(2);
+        let mut rng = Rng::new(self.seed);
+        let half = <A::Item as NumCast>::from(0.5).unwrap();
+
+        // Init
+        let mut xs: Vec<Vector<A>> = (0..len).map(|_| rng.sample_in(bounds)).collect();
+        let mut vs: Vec<Vector<A>> = xs
+            .iter()
+            .map(|x| (&rng.sample_in::<A>(bounds) - x) * half)
+            .collect();
+        let fs = evaluate(objective, &xs, Move::Init);
+        let mut bests: Vec<Pair<A>> = fs
+            .into_iter()
+            .zip(&xs)
+            .map(|(f, x)| Pair::new(f, x.clone()))
+            .collect();
+        let mut global = swarm_best(&bests).clone();
+
+        let max_iter = self.max_iter.limit(dim);
+        for iter in 0..max_iter {
+            objective.iter = iter;
+
+            if let Some(max_fev) = self.max_fev {
+                if objective.fev >= max_fev {
+                    return Err(Error::MaxFev(max_fev));
+                }
+            }
+
+            // Motion
+            for ((x, v), best) in xs.iter_mut().zip(&mut vs).zip(&bests) {
+                for (j, &(lower, upper)) in bounds.iter().enumerate() {
+                    let pull_own = self.cognitive * rng.uniform() * (best.x[j] - x[j]);
+                    let pull_swarm = self.social * rng.uniform() * (global.x[j] - x[j]);
+                    v[j] = self.inertia.mul_add(v[j], pull_own + pull_swarm);
+                    x[j] = x[j] + v[j];
+
+                    // Bounds
+                    if x[j] < lower || upper < x[j] {
+                        x[j] = x[j].max(lower).min(upper);
+                        v[j] = A::Item::zero();
+                    }
+                }
+            }
+
+            // Memory
+            let fs = evaluate(objective, &xs, Move::Trial);
+            for ((f, x), best) in fs.into_iter().zip(&xs).zip(&mut bests) {
+                if f <= best.f {
+                    *best = Pair::new(f, x.clone());
+                }
+            }
+            global = swarm_best(&bests).clone();
+
+            // Termination tests
+            let (f_lo, f_hi) = bests.iter().fold(
+                (A::Item::infinity(), A::Item::neg_infinity()),
+                |(lo, hi), Pair { f, .. }| (lo.min(*f), hi.max(*f)),
+            );
+            let spread = spread(&bests, dim);
+            let test_x = spread.iter().fold(A::Item::zero(), |acc, &x| acc.max(x));
+            let test_f = f_hi - f_lo;
+
+            if test_f <= self.tol_f && test_x <= self.tol_x {
+                return Ok(Output {
+                    f_min: global.f,
+                    x_min: global.x.0,
+                    iter,
+                    fev: objective.fev,
+                    best_ever: objective.best.clone().unwrap(),
+                    termination: Termination::Tolerance,
+                    spread: spread.0,
+                    log: Vec::new(),
+                    phase: Phase::ParticleSwarm,
+                    timing: None,
+                    fitted: None,
+                    trajectory: Vec::new(),
+                });
+            }
+        }
+
+        Err(Error::MaxIter {
+            limit: self.max_iter,
+            iter: max_iter,
+        })
+    }
+}
+
+/// Gives the best of the particle bests.
+fn swarm_best<A: Array>(bests: &[Pair<A>]) -> &Pair<A>
+where
+    A::Item: Float,
+{
+    bests
+        .iter()
+        .min_by(|a, b| a.f.partial_cmp(&b.f).unwrap_or(std::cmp::Ordering::Equal))
+        .unwrap()
+}
@@ -0,0 +1,213 @@
+use array::Array;
+use num_traits::Float;
+
+use crate::global::refine;
+use crate::global::spread;
+use crate::global::Phase;
+use crate::minimizer::MaxIter;
+use crate::minimizer::Minimizer;
+use crate::minimizer::Output;
+use crate::minimizer::Result;
+use crate::minimizer::Termination;
+use crate::objective::BestSoFar;
+use crate::objective::Move;
+use crate::objective::Objective;
+use crate::rng::Rng;
+use crate::simplex::Pair;
+use crate::vector::Vector;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// A structure that holds all the random search parameters.
+///
+/// It samples the box bounds uniformly and keeps the best sample,
+/// the baseline against which the other solvers are judged.
+pub struct RandomSearch<A: Array> {
+    // Sample count parameter
+    samples: MaxIter,
+
+    // Function evaluation parameter
+    max_fev: Option<usize>,
+
+    // Random generator parameter
+    seed: u64,
+
+    // Local refinement parameter
+    pub(crate) local: Option<Minimizer<A>>,
+
+    // Shared best point parameter
+    best_so_far: Option<Arc<BestSoFar<A>>>,
+}
+
+impl<A: Array + Debug> Debug for RandomSearch<A>
+where
+    A::Item: Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RandomSearch")
+            .field("samples", &self.samples)
+            .field("max_fev", &self.max_fev)
+            .field("seed", &self.seed)
+            .field("local", &self.local)
+            .field("best_so_far", &self.best_so_far)
+            .finish()
+    }
+}
+
+impl<A: Array> Default for RandomSearch<A> {
+    fn default() -> RandomSearch<A> {
+        RandomSearch {
+            samples: MaxIter::PerDimension(100),
+            max_fev: None,
+            seed: 0,
+            local: None,
+            best_so_far: None,
+        }
+    }
+}
+
+impl<A: Array> RandomSearch<A>
+where
+    A::Item: Float,
+{
+    /// Sets the sample count.
+    pub fn samples(mut self, samples: MaxIter) -> RandomSearch<A> {
+        self.samples = samples;
+        self
+    }
+
+    /// Sets the function evaluation limit, the local refinement included.
+    ///
+    /// The sampling ends normally there, leaving no evaluation
+    /// to the refinement.
+    pub fn max_fev(mut self, max_fev: usize) -> RandomSearch<A> {
+        self.max_fev = Some(max_fev);
+        self
+    }
+
+    /// Sets the seed of the random generator.
+    pub fn seed(mut self, seed: u64) -> RandomSearch<A> {
+        self.seed = seed;
+        self
+    }
+
+    /// Refines the best sample with the Nelder-Mead `minimizer`.
+    pub fn local(mut self, minimizer: Minimizer<A>) -> RandomSearch<A> {
+        self.local = Some(minimizer);
+        self
+    }
+
+    /// Publishes the best point to `handle` as the search goes,
+    /// for other threads to poll, see `Minimizer::best_so_far`.
+    pub fn best_so_far(mut self, handle: Arc<BestSoFar<A>>) -> RandomSearch<A> {
+        self.best_so_far = Some(handle);
+        self
+    }
+
+    /// Minimizes the function `f` within the box `bounds`,
+    /// given as `(lower, upper)` pairs.
+    pub fn minimize<F>(&self, bounds: &[(A::Item, A::Item)], mut f: F) -> Result<A>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        let mut objective = self.shared(Objective::new(&mut f, false, 0));
+        let output = self.run(bounds, &mut objective, |objective, xs| {
+            xs.iter().map(|x| objective.call(x, Move::Trial)).collect()
+        });
+
+        Ok(refine(
+            self.local.as_ref(),
+            self.best_so_far.as_ref(),
+            self.max_fev,
+            output,
+            f,
+        ))
+    }
+
+    /// Minimizes the function `f` within the box `bounds`,
+    /// evaluating the samples in parallel.
+    ///
+    /// The samples are drawn beforehand, the result does not depend
+    /// on the thread count.
+    #[cfg(feature = "rayon")]
+    pub fn minimize_par<F>(&self, bounds: &[(A::Item, A::Item)], f: F) -> Result<A>
+    where
+        A: Send + Sync,
+        A::Item: Send + Sync,
+        F: Fn(&A) -> A::Item + Sync,
+    {
+        let mut objective = self.shared(Objective::new((), false, 0));
+        let output = self.run(bounds, &mut objective, |objective, xs| {
+            let fs: Vec<A::Item> = xs.par_iter().map(|x| f(x)).collect();
+            for (x, &fx) in xs.iter().zip(&fs) {
+                objective.record(x, fx, Move::Trial);
+            }
+            fs
+        });
+
+        Ok(refine(
+            self.local.as_ref(),
+            self.best_so_far.as_ref(),
+            self.max_fev,
+            output,
+            &f,
+        ))
+    }
+
+    /// Publishes the best point of `objective` to the shared handle, if any.
+    fn shared<F>(&self, objective: Objective<A, F>) -> Objective<A, F> {
+        match &self.best_so_far {
+            Some(handle) => objective.sharing(handle.clone()),
+            None => objective,
+        }
+    }
+
+    /// Draws the samples, `evaluate` giving their values.
+    fn run<F, E>(
+        &self,
+        bounds: &[(A::Item, A::Item)],
+        objective: &mut Objective<A, F>,
+        evaluate: E,
+    ) -> Output<A>
+    where
+        E: FnOnce(&mut Objective<A, F>, &[Vector<A>]) -> Vec<A::Item>,
+    {
+        let dim = bounds.len();
+        let len = self.samples.limit(dim);
+        let len = self.max_fev.map_or(len, |max_fev| len.min(max_fev)).max(1);
+        let mut rng = Rng::new(self.seed);
+
+        let xs: Vec<Vector<A>> = (0..len).map(|_| rng.sample_in(bounds)).collect();
+        let fs = evaluate(objective, &xs);
+        let mut samples: Vec<Pair<A>> = fs
+            .into_iter()
+            .zip(xs)
+            .map(|(f, x)| Pair::new(f, x))
+            .collect();
+        samples.sort_unstable_by(|a, b| a.f.partial_cmp(&b.f).unwrap_or(Ordering::Equal));
+
+        // Spread of the best tenth
+        let spread = spread(&samples[..(len / 10).max(1)], dim);
+        let best = samples.swap_remove(0);
+
+        Output {
+            f_min: best.f,
+            x_min: best.x.0,
+            iter: 0,
+            fev: objective.fev,
+            best_ever: objective.best.clone().unwrap(),
+            termination: Termination::Iterations,
+            spread: spread.0,
+            log: Vec::new(),
+            phase: Phase::RandomSearch,
+            timing: None,
+            fitted: None,
+            trajectory: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use array::Array;
+use num_traits::Float;
+
+use crate::minimizer::Minimizer;
+use crate::minimizer::Result;
+use crate::objective::Evaluation;
+
+impl<A: Array> Minimizer<A>
+where
+    A::Item: Float,
+{
+    /// Replays the minimization recorded in `log` from the seed `x0`,
+    /// the recorded values standing in for the function.
+    ///
+    /// The minimizer must have the parameters of the recorded run,
+    /// see `Minimizer::record`, so that it asks for the same points
+    /// in the same order. It reproduces runs of functions depending
+    /// on external data, for a bug report for instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a point differs from the recorded one,
+    /// or if the log runs out.
+    pub fn replay(&self, x0: &[A::Item], log: &[Evaluation<A>]) -> Result<A> {
+        let mut evaluations = log.iter().enumerate();
+
+        self.minimize(x0, |x: &A| {
+            let (idx, evaluation) = evaluations
+                .next()
+                .expect("the log ran out during the replay");
+            assert!(
+                x.as_ref() == evaluation.x.as_ref(),
+                "the replay diverged from the log at evaluation {}",
+                idx
+            );
+            evaluation.f
+        })
+    }
+}
@@ -0,0 +1,79 @@
+use array::Array;
+
+use crate::minimizer::Output;
+
+use std::fmt;
+use std::fmt::Display;
+use std::fmt::LowerExp;
+
+/// Human-readable summary of an `Output`.
+pub struct Report<'a, A: Array> {
+    output: &'a Output<A>,
+    names: &'a [&'a str],
+}
+
+impl<A: Array> Output<A> {
+    /// Gives a summary naming the parameters after `names`.
+    ///
+    /// Parameters without a name are shown as `x[i]`.
+    pub fn report<'a>(&'a self, names: &'a [&'a str]) -> Report<'a, A> {
+        Report {
+            output: self,
+            names,
+        }
+    }
+}
+
+impl<A: Array> Display for Report<'_, A>
+where
+    A::Item: LowerExp,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let output = self.output;
+        let names: Vec<_> = (0..output.x_min.as_ref().len())
+            .map(|idx| match self.names.get(idx) {
+                Some(name) => name.to_string(),
+                None => format!("x[{}]", idx),
+            })
+            .collect();
+        let width = names.iter().map(String::len).max().unwrap_or(0);
+
+        writeln!(f, "Termination: {}", output.termination)?;
+        writeln!(f, "Phase:       {}", output.phase)?;
+        writeln!(f, "Iterations:  {}", output.iter)?;
+        writeln!(f, "Evaluations: {}", output.fev)?;
+        writeln!(f, "Best value:  {:.6e}", output.f_min)?;
+        if let Some(timing) = &output.timing {
+            writeln!(
+                f,
+                "Timing:      {:?} per evaluation ({:?} to {:?}), {:?} in total, {:?} overhead",
+                timing.mean, timing.min, timing.max, timing.total, timing.overhead
+            )?;
+        }
+        writeln!(f, "Parameters:")?;
+
+        let values = output.x_min.as_ref().iter();
+        let spreads = output.spread.as_ref().iter();
+        for ((name, value), spread) in names.iter().zip(values).zip(spreads) {
+            writeln!(
+                f,
+                "  {:<width$} = {:>13.6e}  (spread {:.1e})",
+                name,
+                value,
+                spread,
+                width = width
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<A: Array> Display for Output<A>
+where
+    A::Item: LowerExp,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.report(&[]).fmt(f)
+    }
+}
@@ -0,0 +1,70 @@
+use array::Array;
+use num_traits::Float;
+use num_traits::NumCast;
+
+use crate::vector::Vector;
+
+/// Seeded pseudo-random generator, xoshiro256** seeded by SplitMix64.
+pub(crate) struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Rng {
+        let mut seed = seed;
+        let mut split_mix = || {
+            seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        };
+
+        Rng {
+            state: [split_mix(), split_mix(), split_mix(), split_mix()],
+        }
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let s = &mut self.state;
+        let result = s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = s[1] << 17;
+        s[2] ^= s[0];
+        s[3] ^= s[1];
+        s[1] ^= s[2];
+        s[0] ^= s[3];
+        s[2] ^= t;
+        s[3] = s[3].rotate_left(45);
+        result
+    }
+
+    /// Gives a uniform sample in `[0, 1)`.
+    pub(crate) fn uniform<T: Float>(&mut self) -> T {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        <T as NumCast>::from(unit).unwrap()
+    }
+
+    /// Gives a standard normal sample, with the Box-Muller transform.
+    pub(crate) fn normal<T: Float>(&mut self) -> T {
+        let u1 = 1.0 - self.uniform::<f64>();
+        let u2 = self.uniform::<f64>();
+        let normal = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+        <T as NumCast>::from(normal).unwrap()
+    }
+
+    /// Gives a uniform index in `0..n`.
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        ((self.next_u64() as u128 * n as u128) >> 64) as usize
+    }
+
+    /// Gives a uniform sample in the box `bounds`.
+    pub(crate) fn sample_in<A: Array>(&mut self, bounds: &[(A::Item, A::Item)]) -> Vector<A>
+    where
+        A::Item: Float,
+    {
+        bounds
+            .iter()
+            .map(|&(lower, upper)| lower + (upper - lower) * self.uniform())
+            .collect()
+    }
+}
@@ -0,0 +1,89 @@
+//! SciPy-compatible options.
+//!
+//! Eases the porting of code written against
+//! `scipy.optimize.minimize(fun, x0, method="Nelder-Mead", options=...)`.
+
+use array::Array;
+use num_traits::Float;
+use num_traits::NumCast;
+
+use crate::minimizer;
+use crate::minimizer::MaxIter;
+use crate::minimizer::Minimizer;
+use crate::minimizer::Result;
+
+/// Options accepted by the SciPy Nelder-Mead method.
+#[derive(Debug, Clone)]
+pub struct Options<T> {
+    /// Absolute error in `x` acceptable for convergence.
+    pub xatol: T,
+
+    /// Absolute error in `f(x)` acceptable for convergence.
+    pub fatol: T,
+
+    /// Maximal number of iterations.
+    pub maxiter: Option<usize>,
+
+    /// Maximal number of function evaluations.
+    pub maxfev: Option<usize>,
+
+    /// Adapts the parameters to the dimension.
+    pub adaptive: bool,
+
+    /// Initial simplex, `x0` is ignored when given.
+    pub initial_simplex: Option<Vec<Vec<T>>>,
+}
+
+impl<T: Float> Default for Options<T> {
+    fn default() -> Options<T> {
+        Options {
+            xatol: minimizer::tolerance(1e-4),
+            fatol: minimizer::tolerance(1e-4),
+            maxiter: None,
+            maxfev: None,
+            adaptive: false,
+            initial_simplex: None,
+        }
+    }
+}
+
+/// Minimizes the function `fun` with the seed `x0`,
+/// with the initial simplex and the convergence tests of SciPy.
+///
+/// The initial simplex moves each coordinate by 5 %, or by 0.00025 when zero.
+/// The minimization converges once every vertex is within `xatol` of the best
+/// one on each coordinate, and within `fatol` of its value.
+/// When neither `maxiter` nor `maxfev` is set, both default to `200 * n`.
+///
+/// The steps may still differ from SciPy on ties between the candidates.
+pub fn minimize<A, F>(fun: F, x0: &[A::Item], options: &Options<A::Item>) -> Result<A>
+where
+    A: Array,
+    A::Item: Float,
+    F: FnMut(&A) -> A::Item,
+{
+    let dim = match &options.initial_simplex {
+        Some(vertices) => vertices.first().map_or(0, Vec::len),
+        None => x0.len(),
+    };
+
+    let (max_iter, max_fev) = match (options.maxiter, options.maxfev) {
+        (None, None) => (MaxIter::PerDimension(200), Some(200 * dim)),
+        (Some(maxiter), maxfev) => (MaxIter::Absolute(maxiter), maxfev),
+        (None, Some(maxfev)) => (MaxIter::Absolute(usize::MAX), Some(maxfev)),
+    };
+
+    let mut minimizer = Minimizer::default().max_iter(max_iter);
+    minimizer.step = <A::Item as NumCast>::from(0.05).unwrap();
+    minimizer.step_zero = <A::Item as NumCast>::from(0.00025).unwrap();
+    minimizer.tol_f = options.fatol;
+    minimizer.tol_x = options.xatol;
+    minimizer.tol_x_all = true;
+    minimizer.adaptive = options.adaptive;
+    minimizer.max_fev = max_fev;
+
+    match &options.initial_simplex {
+        Some(vertices) => minimizer.minimize_simplex(vertices, fun),
+        None => minimizer.minimize(x0, fun),
+    }
+}
@@ -4,67 +4,210 @@ use num_traits::NumCast;
 use num_traits::One;
 use num_traits::Zero;
 
+use crate::linalg;
 use crate::minimizer::Minimizer;
+use crate::objective::ObjectiveValue;
 use crate::vector::Vector;
 
 use std::cmp::Ordering;
 use std::ops::Mul;
 
 #[derive(Clone)]
-pub(crate) struct Pair<A: Array> {
-    pub(crate) f: A::Item,
+pub(crate) struct Pair<A: Array, V = <A as Array>::Item> {
+    pub(crate) f: V,
     pub(crate) x: Vector<A>,
 }
 
-impl<A: Array> Pair<A> {
-    pub(crate) fn new(f: A::Item, x: Vector<A>) -> Pair<A> {
+impl<A: Array, V> Pair<A, V> {
+    pub(crate) fn new(f: V, x: Vector<A>) -> Pair<A, V> {
         Pair { f, x }
     }
 }
 
-pub(crate) struct Simplex<A: Array> {
-    pairs: Vec<Pair<A>>,
+pub(crate) struct Simplex<A: Array, V = <A as Array>::Item> {
+    pairs: Vec<Pair<A, V>>,
+    // Points evaluated as one batch, kept between batches
+    batch: Vec<A>,
     dim: usize,
     inv_dim: A::Item,
 }
 
-impl<A: Array> Simplex<A> {
-    pub(crate) fn new<F>(slice: &[A::Item], mut f: F, minimizer: &Minimizer<A>) -> Simplex<A>
+impl<A: Array, V> Simplex<A, V> {
+    /// Builds the simplex around the seed `x0`, its first vertex,
+    /// evaluating it unless its value `f0` is given.
+    ///
+    /// The vertices are evaluated as one batch, as in the other builders.
+    pub(crate) fn new<F>(
+        x0: Vector<A>,
+        f0: Option<V>,
+        f: F,
+        minimizer: &Minimizer<A>,
+    ) -> Simplex<A, V>
     where
-        F: FnMut(&A) -> A::Item,
+        F: FnMut(&[A]) -> Vec<V>,
         A::Item: Float,
     {
-        let dim = <A::Item as NumCast>::from(slice.len()).unwrap();
-        let inv_dim = dim.recip();
-        let x0 = Vector::from_slice(slice);
+        let len = x0.iter().len();
+        let storage = (Vec::with_capacity(len + 1), Vec::with_capacity(len + 1));
+        Simplex::with_storage(storage, x0, f0, f, minimizer)
+    }
 
-        let mut pairs = Vec::new();
-        let pair = Pair::new(f(&x0), x0.clone());
-        pairs.push(pair);
+    /// Builds the simplex as `new` does, in the storage of a previous one.
+    pub(crate) fn with_storage<F>(
+        (mut pairs, mut batch): (Vec<Pair<A, V>>, Vec<A>),
+        x0: Vector<A>,
+        f0: Option<V>,
+        mut f: F,
+        minimizer: &Minimizer<A>,
+    ) -> Simplex<A, V>
+    where
+        F: FnMut(&[A]) -> Vec<V>,
+        A::Item: Float,
+    {
+        let len = x0.iter().len();
+        let inv_dim = <A::Item as NumCast>::from(len).unwrap().recip();
 
-        for (idx, _) in x0.iter().enumerate() {
+        // The seed is evaluated first, unless known
+        let seeded = f0.is_none();
+        batch.clear();
+        if seeded {
+            batch.push(x0.0.clone());
+        }
+        for idx in 0..len {
             let mut x = x0.clone();
+            x[idx] = perturb(x[idx], minimizer);
+            batch.push(x.0);
+        }
+
+        let mut fs = f(&batch).into_iter();
+        let f0 = f0.unwrap_or_else(|| fs.next().unwrap());
+
+        // The seed goes first
+        pairs.clear();
+        pairs.reserve(len + 1);
+        pairs.push(Pair::new(f0, x0));
+        let perturbed = batch.drain(..).skip(if seeded { 1 } else { 0 });
+        pairs.extend(perturbed.zip(fs).map(|(x, f)| Pair::new(f, Vector(x))));
 
-            {
-                let xi = &mut x[idx];
-                *xi = if xi.is_zero() {
-                    minimizer.step_zero
-                } else {
-                    *xi * (A::Item::one() + minimizer.step)
-                };
+        Simplex {
+            pairs,
+            batch,
+            dim: len,
+            inv_dim,
+        }
+    }
+
+    /// Builds the simplex from user-provided vertices.
+    ///
+    /// Missing vertices are generated around the centroid of the given ones,
+    /// along the coordinate axes keeping the simplex non-degenerate.
+    pub(crate) fn from_vertices<T, F>(
+        vertices: &[T],
+        mut f: F,
+        minimizer: &Minimizer<A>,
+    ) -> Simplex<A, V>
+    where
+        T: AsRef<[A::Item]>,
+        F: FnMut(&[A]) -> Vec<V>,
+        A::Item: Float,
+    {
+        assert!(!vertices.is_empty(), "at least one vertex is required");
+        let dim = vertices[0].as_ref().len();
+        assert!(
+            vertices.len() <= dim + 1,
+            "at most n + 1 vertices are expected"
+        );
+        let inv_dim = <A::Item as NumCast>::from(dim).unwrap().recip();
+
+        let mut xs: Vec<Vector<A>> = vertices
+            .iter()
+            .map(|vertex| {
+                let vertex = vertex.as_ref();
+                assert_eq!(vertex.len(), dim, "vertex dimension mismatch");
+                Vector::from_slice(vertex)
+            })
+            .collect();
+
+        if xs.len() <= dim {
+            let inv_len = <A::Item as NumCast>::from(xs.len()).unwrap().recip();
+            let centroid = xs
+                .iter()
+                .fold(Vector::zeros(dim), |acc, x| acc + x)
+                .mul(inv_len);
+
+            // Orthonormal basis of the edges
+            let mut basis = Vec::new();
+            let (first, rest) = xs.split_first().unwrap();
+            for x in rest {
+                orthonormalize(&mut basis, x - first);
             }
 
-            let pair = Pair::new(f(&x), x.clone());
-            pairs.push(pair);
+            for idx in 0..dim {
+                if xs.len() > dim {
+                    break;
+                }
+
+                let mut axis = Vector::<A>::zeros(dim);
+                axis[idx] = A::Item::one();
+                if !orthonormalize(&mut basis, axis) {
+                    continue;
+                }
+
+                let mut x = centroid.clone();
+                x[idx] = perturb(x[idx], minimizer);
+                xs.push(x);
+            }
         }
 
+        let mut batch: Vec<A> = xs.into_iter().map(|x| x.0).collect();
+        let fs = f(&batch);
+        let pairs = fs
+            .into_iter()
+            .zip(batch.drain(..))
+            .map(|(f, x)| Pair::new(f, Vector(x)))
+            .collect();
+
         Simplex {
             pairs,
-            dim: slice.len(),
+            batch,
+            dim,
             inv_dim,
         }
     }
 
+    /// Rebuilds the simplex around `x0`, keeping its storage.
+    pub(crate) fn rebuild<F>(&mut self, x0: Vector<A>, f: F, minimizer: &Minimizer<A>)
+    where
+        F: FnMut(&[A]) -> Vec<V>,
+        A::Item: Float,
+    {
+        let storage = (
+            std::mem::take(&mut self.pairs),
+            std::mem::take(&mut self.batch),
+        );
+        *self = Simplex::with_storage(storage, x0, None, f, minimizer);
+    }
+
+    /// Gives back the storage of the vertices and of the batches.
+    pub(crate) fn into_storage(self) -> (Vec<Pair<A, V>>, Vec<A>) {
+        (self.pairs, self.batch)
+    }
+
+    /// Gives the emptied storage of the batches, to fill with the points
+    /// to evaluate at once.
+    pub(crate) fn batch(&mut self) -> &mut Vec<A> {
+        self.batch.clear();
+        &mut self.batch
+    }
+
+    pub(crate) fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub(crate) fn pairs(&self) -> &[Pair<A, V>] {
+        &self.pairs
+    }
+
     pub(crate) fn centroid(&self) -> Vector<A>
     where
         A::Item: Float,
@@ -80,7 +223,7 @@ impl<A: Array> Simplex<A> {
 
     pub(crate) fn sort_unstable(&mut self)
     where
-        A::Item: Float,
+        V: PartialOrd,
     {
         self.pairs
             .sort_unstable_by(|a, b| a.f.partial_cmp(&b.f).unwrap_or(Ordering::Equal));
@@ -88,39 +231,172 @@ impl<A: Array> Simplex<A> {
 
     /// Gives the best estimation,
     /// but it requires to call `sort_unstable` once before.
-    pub(crate) fn best(&self) -> Option<&Pair<A>> {
+    pub(crate) fn best(&self) -> Option<&Pair<A, V>> {
         self.pairs.first()
     }
 
+    /// Gives the best estimation mutably,
+    /// but it requires to call `sort_unstable` once before.
+    pub(crate) fn best_mut(&mut self) -> Option<&mut Pair<A, V>> {
+        self.pairs.first_mut()
+    }
+
     /// Gives the worst estimation,
     /// but it requires to call `sort_unstable` once before.
-    pub(crate) fn worst(&self) -> Option<&Pair<A>> {
+    pub(crate) fn worst(&self) -> Option<&Pair<A, V>> {
         self.pairs.last()
     }
 
     /// Gives the second worst estimation,
     /// but it requires to call `sort_unstable` once before.
-    pub(crate) fn second_worst(&self) -> Option<&Pair<A>> {
+    pub(crate) fn second_worst(&self) -> Option<&Pair<A, V>> {
         let (_last, rest) = self.pairs.split_last()?;
         let (second_to_last, _rest) = rest.split_last()?;
         Some(second_to_last)
     }
 
-    pub(crate) fn shrink<F>(&mut self, mut f: F, minimizer: &Minimizer<A>)
+    /// Gives the simplex gradient, the gradient of the linear interpolation
+    /// of the function on the simplex vertices,
+    /// but it requires to call `sort_unstable` once before.
+    pub(crate) fn gradient(&self) -> Option<Vector<A>>
+    where
+        V: ObjectiveValue<A::Item>,
+        A::Item: Float,
+    {
+        let (best, rest) = self.pairs.split_first()?;
+        let mut edges = Vec::with_capacity(self.dim * self.dim);
+        let mut diffs = Vec::with_capacity(self.dim);
+        for pair in rest {
+            edges.extend((&pair.x - &best.x).iter().copied());
+            diffs.push(pair.f.scalar() - best.f.scalar());
+        }
+
+        linalg::solve(&mut edges, &mut diffs)?;
+        Some(diffs.into_iter().collect())
+    }
+
+    /// Gives the largest distance from the best vertex,
+    /// but it requires to call `sort_unstable` once before.
+    pub(crate) fn diameter(&self) -> A::Item
     where
-        F: FnMut(&A) -> A::Item,
+        A::Item: Float,
+    {
+        let best = &self.best().unwrap().x;
+        self.pairs
+            .iter()
+            .skip(1)
+            .map(|Pair { x, .. }| (x - best).norm())
+            .fold(A::Item::zero(), A::Item::max)
+    }
+
+    /// Gives the range of each coordinate over the vertices.
+    pub(crate) fn spread(&self) -> Vector<A>
+    where
+        A::Item: Float,
+    {
+        (0..self.dim)
+            .map(|idx| {
+                let (min, max) = self.pairs.iter().fold(
+                    (A::Item::infinity(), A::Item::neg_infinity()),
+                    |(min, max), Pair { x, .. }| (min.min(x[idx]), max.max(x[idx])),
+                );
+                max - min
+            })
+            .collect()
+    }
+
+    /// Shrinks the simplex towards its best vertex,
+    /// evaluating the moved vertices as one batch.
+    pub(crate) fn shrink<F>(&mut self, mut f: F, d: A::Item)
+    where
+        F: FnMut(&[A]) -> Vec<V>,
         A::Item: Float,
     {
         let best = self.best().unwrap().x.clone();
         for pair in self.pairs.iter_mut().skip(1) {
-            pair.x = pair.x.clone() * minimizer.d;
-            pair.x.scaled_add(A::Item::one() - minimizer.d, &best);
-            pair.f = f(&pair.x);
+            pair.x.iter_mut().for_each(|xi| *xi = *xi * d);
+            pair.x.scaled_add(A::Item::one() - d, &best);
+        }
+
+        self.batch.clear();
+        self.batch
+            .extend(self.pairs[1..].iter().map(|pair| pair.x.0.clone()));
+        for (pair, fx) in self.pairs.iter_mut().skip(1).zip(f(&self.batch)) {
+            pair.f = fx;
         }
     }
 
-    pub(crate) fn update(&mut self, pair: Pair<A>) {
+    pub(crate) fn update(&mut self, pair: Pair<A, V>) {
         self.pairs.pop();
         self.pairs.push(pair);
     }
 }
+
+/// Storage kept between minimizations, sparing its reallocation.
+///
+/// It holds the vertices of the simplex and the points
+/// evaluated as one batch, such as the speculative candidates.
+pub struct Workspace<A: Array> {
+    pairs: Vec<Pair<A>>,
+    batch: Vec<A>,
+}
+
+impl<A: Array> Workspace<A> {
+    /// Creates an empty workspace, to pass to `Minimizer::minimize_in`.
+    pub fn new() -> Workspace<A> {
+        Workspace {
+            pairs: Vec::new(),
+            batch: Vec::new(),
+        }
+    }
+
+    pub(crate) fn take(&mut self) -> (Vec<Pair<A>>, Vec<A>) {
+        (
+            std::mem::take(&mut self.pairs),
+            std::mem::take(&mut self.batch),
+        )
+    }
+
+    pub(crate) fn give(&mut self, (pairs, batch): (Vec<Pair<A>>, Vec<A>)) {
+        self.pairs = pairs;
+        self.batch = batch;
+    }
+}
+
+impl<A: Array> Default for Workspace<A> {
+    fn default() -> Workspace<A> {
+        Workspace::new()
+    }
+}
+
+/// Moves a coordinate of the seed to build the initial simplex.
+fn perturb<A: Array>(xi: A::Item, minimizer: &Minimizer<A>) -> A::Item
+where
+    A::Item: Float,
+{
+    if xi.is_zero() {
+        minimizer.step_zero
+    } else {
+        xi * (A::Item::one() + minimizer.step)
+    }
+}
+
+/// Adds `v` to the orthonormal `basis` if it is not already spanned by it.
+fn orthonormalize<A: Array>(basis: &mut Vec<Vector<A>>, mut v: Vector<A>) -> bool
+where
+    A::Item: Float,
+{
+    let norm = v.norm();
+    for u in basis.iter() {
+        let proj = u.dot(&v);
+        v.scaled_add(-proj, u);
+    }
+
+    let residual = v.norm();
+    if residual <= A::Item::epsilon().sqrt() * norm {
+        return false;
+    }
+
+    basis.push(v * residual.recip());
+    true
+}
@@ -0,0 +1,191 @@
+use array::Array;
+use num_traits::Float;
+use num_traits::NumCast;
+use num_traits::One;
+
+use crate::global::Phase;
+use crate::minimizer::Error;
+use crate::minimizer::MaxIter;
+use crate::minimizer::Output;
+use crate::minimizer::Result;
+use crate::minimizer::Termination;
+use crate::objective::Move;
+use crate::objective::Objective;
+use crate::rng::Rng;
+use crate::vector::Vector;
+
+/// A structure that holds all the SPSA parameters.
+///
+/// The simultaneous perturbation stochastic approximation (Spall, J. C., 1998)
+/// estimates the gradient from two evaluations along a random direction
+/// at each iteration, whatever the dimension, which suits noisy functions.
+#[derive(Debug)]
+pub struct Spsa<A: Array> {
+    // Step gain parameter
+    a: A::Item,
+
+    // Perturbation gain parameter
+    c: A::Item,
+
+    // Stability constant parameter, a tenth of the iterations when unset
+    stability: Option<A::Item>,
+
+    // Step decay exponent parameter
+    alpha: A::Item,
+
+    // Perturbation decay exponent parameter
+    gamma: A::Item,
+
+    // Iterations parameter
+    max_iter: MaxIter,
+
+    // Function evaluations parameter
+    max_fev: Option<usize>,
+
+    // Random generator parameter
+    seed: u64,
+}
+
+impl<A: Array> Default for Spsa<A>
+where
+    A::Item: Float,
+{
+    fn default() -> Spsa<A> {
+        Spsa {
+            a: <A::Item as NumCast>::from(0.1).unwrap(),
+            c: <A::Item as NumCast>::from(0.1).unwrap(),
+            stability: None,
+            alpha: <A::Item as NumCast>::from(0.602).unwrap(),
+            gamma: <A::Item as NumCast>::from(0.101).unwrap(),
+            max_iter: MaxIter::Absolute(1000),
+            max_fev: None,
+            seed: 0,
+        }
+    }
+}
+
+impl<A: Array> Spsa<A>
+where
+    A::Item: Float,
+{
+    /// Sets the step gain `a`, the step at iteration `k`
+    /// being `a / (k + 1 + stability)^alpha` times the gradient estimate.
+    pub fn a(mut self, a: A::Item) -> Spsa<A> {
+        self.a = a;
+        self
+    }
+
+    /// Sets the perturbation gain `c`, the perturbation at iteration `k`
+    /// being `c / (k + 1)^gamma`, about the noise standard deviation.
+    pub fn c(mut self, c: A::Item) -> Spsa<A> {
+        self.c = c;
+        self
+    }
+
+    /// Sets the stability constant, damping the first steps.
+    pub fn stability(mut self, stability: A::Item) -> Spsa<A> {
+        self.stability = Some(stability);
+        self
+    }
+
+    /// Sets the decay exponents of the step and of the perturbation.
+    pub fn exponents(mut self, alpha: A::Item, gamma: A::Item) -> Spsa<A> {
+        self.alpha = alpha;
+        self.gamma = gamma;
+        self
+    }
+
+    /// Sets the iteration schedule length.
+    pub fn max_iter(mut self, max_iter: MaxIter) -> Spsa<A> {
+        self.max_iter = max_iter;
+        self
+    }
+
+    /// Sets the function evaluation limit.
+    pub fn max_fev(mut self, max_fev: usize) -> Spsa<A> {
+        self.max_fev = Some(max_fev);
+        self
+    }
+
+    /// Sets the seed of the random generator.
+    pub fn seed(mut self, seed: u64) -> Spsa<A> {
+        self.seed = seed;
+        self
+    }
+
+    /// Minimizes the function `f` with the seed `x0`.
+    ///
+    /// The whole schedule is run, the last iterate being evaluated
+    /// once more to give the minimum value.
+    pub fn minimize<F>(&self, x0: &[A::Item], f: F) -> Result<A>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        let one = A::Item::one();
+        let two = one + one;
+
+        let dim = x0.len();
+        let max_iter = self.max_iter.limit(dim);
+        let stability = self
+            .stability
+            .unwrap_or_else(|| <A::Item as NumCast>::from(max_iter / 10).unwrap());
+        let mut rng = Rng::new(self.seed);
+        let mut objective = Objective::new(f, false, 0);
+
+        let mut x = Vector::<A>::from_slice(x0);
+        for iter in 0..max_iter {
+            objective.iter = iter;
+
+            if let Some(max_fev) = self.max_fev {
+                if objective.fev >= max_fev {
+                    return Err(Error::MaxFev(max_fev));
+                }
+            }
+
+            // Gains
+            let k = <A::Item as NumCast>::from(iter + 1).unwrap();
+            let ak = self.a / (k + stability).powf(self.alpha);
+            let ck = self.c / k.powf(self.gamma);
+
+            // Rademacher perturbation
+            let delta: Vec<A::Item> = (0..dim)
+                .map(|_| if rng.next_u64() >> 63 == 0 { one } else { -one })
+                .collect();
+            let mut plus = x.clone();
+            let mut minus = x.clone();
+            for ((p, m), &di) in plus.iter_mut().zip(minus.iter_mut()).zip(&delta) {
+                *p = di.mul_add(ck, *p);
+                *m = di.mul_add(-ck, *m);
+            }
+            let diff = objective.call(&plus, Move::Trial) - objective.call(&minus, Move::Trial);
+
+            // Step along the gradient estimate
+            let scale = ak * diff / (two * ck);
+            for (xi, &di) in x.iter_mut().zip(&delta) {
+                *xi = *xi - scale / di;
+            }
+        }
+
+        let f_min = objective.call(&x, Move::Reevaluation);
+
+        // Last perturbation width
+        let k = <A::Item as NumCast>::from(max_iter.max(1)).unwrap();
+        let width = two * self.c / k.powf(self.gamma);
+        let spread: Vector<A> = (0..dim).map(|_| width).collect();
+
+        Ok(Output {
+            f_min,
+            x_min: x.0,
+            iter: max_iter,
+            fev: objective.fev,
+            best_ever: objective.best.unwrap(),
+            termination: Termination::Iterations,
+            spread: spread.0,
+            log: Vec::new(),
+            phase: Phase::Spsa,
+            timing: None,
+            fitted: None,
+            trajectory: Vec::new(),
+        })
+    }
+}
@@ -0,0 +1,39 @@
+use array::Array;
+
+use crate::minimizer::Point;
+use crate::objective::Move;
+
+use std::fmt::Debug;
+
+/// Outcome of a step.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step<T> {
+    /// Replaces the worst vertex with the candidate evaluated at that rank.
+    Replace(usize),
+    /// Shrinks every vertex towards the best one by that factor.
+    Shrink(T),
+    /// Keeps the simplex as is.
+    Keep,
+}
+
+/// Candidate generation step of the Nelder-Mead minimizer,
+/// for variants reusing its simplex bookkeeping, termination tests and output.
+///
+/// A strategy holding a state, such as a random generator,
+/// keeps it behind a lock, the minimizer being shared.
+pub trait Strategy<A: Array>: Debug + Send + Sync {
+    /// Gives the step from the `vertices`, sorted from the best one,
+    /// `evaluate` giving the value of each candidate.
+    ///
+    /// The values are the scalars of the function values, see `ObjectiveValue`.
+    ///
+    /// # Panics
+    ///
+    /// The minimizer panics if the step replaces the worst vertex
+    /// with a candidate rank beyond the evaluated ones.
+    fn step(
+        &self,
+        vertices: &[Point<A>],
+        evaluate: &mut dyn FnMut(&A, Move) -> A::Item,
+    ) -> Step<A::Item>;
+}
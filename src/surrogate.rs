@@ -0,0 +1,113 @@
+use array::Array;
+use num_traits::Float;
+use num_traits::NumCast;
+use num_traits::One;
+use num_traits::Zero;
+
+use crate::linalg;
+use crate::minimizer::Point;
+use crate::objective::ObjectiveValue;
+use crate::vector::Vector;
+
+/// Number of coefficients of a quadratic model in dimension `dim`.
+pub(crate) fn len(dim: usize) -> usize {
+    (dim + 1) * (dim + 2) / 2
+}
+
+/// Fits a quadratic model on `samples` by least squares
+/// and gives its minimizer, if the model is convex.
+pub(crate) fn minimizer<'a, A, V, I>(samples: I, center: &Vector<A>) -> Option<Vector<A>>
+where
+    A: Array + 'a,
+    A::Item: Float,
+    V: ObjectiveValue<A::Item> + 'a,
+    I: IntoIterator<Item = &'a Point<A, V>>,
+{
+    fit(samples, center).map(|(x, _)| x)
+}
+
+/// Fits a quadratic model on `samples` by least squares
+/// and gives its minimizer and minimum, if the model is convex.
+///
+/// The model is centered on `center` to keep the system well conditioned.
+pub(crate) fn fit<'a, A, V, I>(samples: I, center: &Vector<A>) -> Option<(Vector<A>, A::Item)>
+where
+    A: Array + 'a,
+    A::Item: Float,
+    V: ObjectiveValue<A::Item> + 'a,
+    I: IntoIterator<Item = &'a Point<A, V>>,
+{
+    let dim = center.iter().len();
+    let len = len(dim);
+
+    // Normal equations
+    let mut lhs = vec![A::Item::zero(); len * len];
+    let mut rhs = vec![A::Item::zero(); len];
+    let mut features = Vec::with_capacity(len);
+    let mut count = 0;
+    for Point { f, x } in samples {
+        let u: Vec<_> = x
+            .as_ref()
+            .iter()
+            .zip(center.iter())
+            .map(|(&xi, &ci)| xi - ci)
+            .collect();
+
+        features.clear();
+        features.push(A::Item::one());
+        features.extend(u.iter().copied());
+        for i in 0..dim {
+            for j in i..dim {
+                features.push(u[i] * u[j]);
+            }
+        }
+
+        for (row, &phi_row) in features.iter().enumerate() {
+            for (col, &phi_col) in features.iter().enumerate() {
+                lhs[row * len + col] = phi_row.mul_add(phi_col, lhs[row * len + col]);
+            }
+            rhs[row] = phi_row.mul_add(f.scalar(), rhs[row]);
+        }
+        count += 1;
+    }
+
+    if count < len {
+        return None;
+    }
+    linalg::solve(&mut lhs, &mut rhs)?;
+
+    // Gradient and Hessian at the center
+    let two = <A::Item as NumCast>::from(2.0).unwrap();
+    let mut grad: Vec<_> = rhs[1..=dim].iter().map(|&g| -g).collect();
+    let mut hess = vec![A::Item::zero(); dim * dim];
+    let mut coefs = rhs[dim + 1..].iter();
+    for i in 0..dim {
+        for j in i..dim {
+            let coef = *coefs.next().unwrap();
+            if i == j {
+                hess[i * dim + i] = two * coef;
+            } else {
+                hess[i * dim + j] = coef;
+                hess[j * dim + i] = coef;
+            }
+        }
+    }
+
+    // Convexity
+    linalg::cholesky(&mut hess.clone(), dim)?;
+
+    linalg::solve(&mut hess, &mut grad)?;
+
+    // Minimum, f(c + u) = f(c) + g.u / 2 at the model minimizer
+    let half = two.recip();
+    let min = rhs[1..=dim]
+        .iter()
+        .zip(&grad)
+        .fold(rhs[0], |acc, (&g, &u)| (half * g).mul_add(u, acc));
+    let x = grad
+        .into_iter()
+        .zip(center.iter())
+        .map(|(u, &c)| c + u)
+        .collect();
+    Some((x, min))
+}
@@ -1,4 +1,41 @@
+use crate::bayes::BayesianOptimization;
+use crate::cache::Cache;
+use crate::cma::CmaEs;
+use crate::cobyla::Cobyla;
+use crate::convergence::Convergence;
+use crate::de::DifferentialEvolution;
+use crate::direct::Direct;
+use crate::evaluator::AsyncEvaluator;
+use crate::evaluator::Threads;
+use crate::external::External;
+use crate::external::ExternalError;
+use crate::external::ExternalInput;
+use crate::global::Phase;
+use crate::minimizer::Error;
+use crate::minimizer::MaxIter;
 use crate::minimizer::Minimizer;
+use crate::minimizer::Output;
+use crate::minimizer::Point;
+use crate::minimizer::Shape;
+use crate::minimizer::ShrinkReaction;
+use crate::minimizer::Termination;
+use crate::objective::BestSoFar;
+use crate::objective::Move;
+use crate::objective::ObjectiveValue;
+use crate::objective::PanicPolicy;
+use crate::objective::Parameters;
+use crate::optimizer::Method;
+use crate::optimizer::Optimizer;
+use crate::polish::Polish;
+use crate::portfolio::Portfolio;
+use crate::pso::ParticleSwarm;
+use crate::random::RandomSearch;
+use crate::scipy;
+use crate::simplex::Workspace;
+use crate::spsa::Spsa;
+use crate::strategy::Step;
+use crate::strategy::Strategy;
+use crate::weights::Weights;
 
 #[test]
 fn minimize_square_1d() {
@@ -66,3 +103,1875 @@ fn minimize_abs() {
 
     assert!((result.f_min - expected).abs() < 1e-9);
 }
+
+#[test]
+fn minimize_rosenbrock_simplex() {
+    let minimizer = Minimizer::<[f64; 2]>::default();
+    let expected = 0.0;
+    let vertices = [[-1.0, 1.0], [-1.1, 1.0], [-1.0, 1.1]];
+    let result = minimizer
+        .minimize_simplex(&vertices, |x| {
+            (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0].powi(2)).powi(2)
+        })
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!(" iter = {:?}", result.iter);
+
+    assert!((result.f_min - expected).abs() < 1e-6);
+}
+
+#[test]
+fn scipy_adaptive() {
+    let options = scipy::Options {
+        xatol: 1e-8,
+        fatol: 1e-8,
+        adaptive: true,
+        ..scipy::Options::default()
+    };
+    let expected = 0.0;
+    let result = scipy::minimize::<[f64; 3], _>(
+        |x| x.iter().map(|xi| (xi - 1.0).powi(2)).sum(),
+        &[0.0, 0.0, 0.0],
+        &options,
+    )
+    .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!(" iter = {:?}", result.iter);
+
+    assert!((result.f_min - expected).abs() < 1e-9);
+}
+
+#[test]
+fn scipy_simplex() {
+    let options = scipy::Options::default();
+    let mut xs = Vec::new();
+    let result = scipy::minimize::<[f64; 2], _>(
+        |x| {
+            xs.push(*x);
+            (x[0] - 3.0).powi(2) + (x[1] + 2.0).powi(2)
+        },
+        &[2.0, 0.0],
+        &options,
+    )
+    .unwrap();
+
+    // Nonzero coordinates move by 5 %, zero ones by 0.00025
+    assert_eq!(xs[..3], [[2.0, 0.0], [2.1, 0.0], [2.0, 0.00025]]);
+    // Every vertex is within `xatol` of the best one
+    for spread in result.spread {
+        assert!(spread <= 2.0 * options.xatol);
+    }
+}
+
+#[test]
+fn scipy_maxfev() {
+    let options = scipy::Options {
+        maxfev: Some(10),
+        ..scipy::Options::default()
+    };
+    let result =
+        scipy::minimize::<[f64; 2], _>(|x| x[0].cosh() + x[1].cosh(), &[1.0, 1.0], &options);
+
+    assert!(matches!(result, Err(Error::MaxFev(10))));
+}
+
+#[test]
+fn minimize_partial_simplex() {
+    let minimizer = Minimizer::<[f64; 3]>::default();
+    let expected = 0.0;
+    let vertices = [[1.0, 1.0, 1.0], [1.0, 1.0, 1.5]];
+    let result = minimizer
+        .minimize_simplex(&vertices, |x| x.iter().map(|xi| xi.powi(2)).sum())
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!(" iter = {:?}", result.iter);
+
+    assert!((result.f_min - expected).abs() < 1e-6);
+}
+
+#[test]
+fn minimize_unbounded() {
+    let minimizer = Minimizer::<[f64; 1]>::default();
+    let result = minimizer.minimize(&[1.0], |x| x[0]);
+
+    match result {
+        Err(Error::Diverged(tail)) => {
+            println!("tail = {:?}", tail);
+            assert!(tail.last().unwrap().x[0] < -1e6);
+        }
+        _ => panic!("expected divergence"),
+    }
+
+    let plane = |x: &[f64; 2]| x[0] + 2.0 * x[1];
+    let result = Minimizer::default().minimize(&[1.0, 1.0], plane);
+    assert!(matches!(result, Err(Error::Diverged(_))));
+
+    let result = Minimizer::default().max_x(1e3).minimize(&[1.0, 1.0], plane);
+    match result {
+        Err(Error::Diverged(tail)) => {
+            assert!(tail.last().unwrap().x.iter().all(|xi| xi.abs() < 1e4))
+        }
+        _ => panic!("expected divergence"),
+    }
+
+    // A far minimum is not mistaken for a divergence
+    let far = |x: &[f64; 1]| (x[0] - 1e6).powi(2);
+    let result = Minimizer::default().minimize(&[0.0], far).unwrap();
+    assert!((result.x_min[0] - 1e6).abs() < 1e-2);
+}
+
+#[test]
+fn minimize_best_ever() {
+    // A lucky evaluation of a noisy function, lost once re-evaluated
+    let mut fev = 0;
+    let mut lucky = None;
+    let minimizer = Minimizer::<[f64; 2]>::default().reevaluate(true);
+    let result = minimizer
+        .minimize(&[1.0, 1.0], |x| {
+            fev += 1;
+            let f = x[0].powi(2) + 2.0 * x[1].powi(2);
+            if fev == 20 {
+                lucky = Some((f - 100.0, *x));
+                return f - 100.0;
+            }
+            f
+        })
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("best_ever = {:?}", result.best_ever);
+
+    let (f, x) = lucky.unwrap();
+    assert!(result.f_min > -1e-6);
+    assert_eq!(result.best_ever.f, f);
+    assert_eq!(result.best_ever.x, x);
+}
+
+#[test]
+fn minimize_max_iter_absolute() {
+    let minimizer = Minimizer::<[f64; 4]>::default().max_iter(MaxIter::Absolute(5));
+    let result = minimizer.minimize(&[1.0, 2.0, 3.0, 4.0], |x| {
+        x.iter().map(|xi| xi.powi(2)).sum()
+    });
+
+    assert!(matches!(
+        result,
+        Err(Error::MaxIter {
+            limit: MaxIter::Absolute(5),
+            iter: 5,
+        })
+    ));
+}
+
+#[test]
+fn minimize_tol_grad() {
+    let minimizer = Minimizer::<[f64; 2]>::default().tol_grad(1e-8);
+    let expected = 0.0;
+    let result = minimizer
+        .minimize(&[1.0, 1.0], |x| x[0].powi(2) + 3.0 * x[1].powi(2))
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!(" iter = {:?}", result.iter);
+
+    assert_eq!(result.termination, Termination::Gradient);
+    assert!((result.f_min - expected).abs() < 1e-6);
+}
+
+#[test]
+fn report() {
+    let minimizer = Minimizer::<[f64; 2]>::default();
+    let result = minimizer
+        .minimize(&[1.0, 1.0], |x| (x[0] - 2.0).powi(2) + x[1].powi(2))
+        .unwrap();
+    let report = result.report(&["offset"]).to_string();
+
+    println!("{}", report);
+
+    assert!(report.contains("Termination: Function value and point tolerances reached"));
+    assert!(report.contains("  offset = "));
+    assert!(report.contains("  x[1]   = "));
+}
+
+#[test]
+fn minimize_record() {
+    let minimizer = Minimizer::<[f64; 2]>::default().record(true);
+    let result = minimizer
+        .minimize(&[1.0, 1.0], |x| x[0].powi(2) + x[1].powi(2))
+        .unwrap();
+
+    assert_eq!(result.log.len(), result.fev);
+    assert!(result
+        .log
+        .iter()
+        .take(3)
+        .all(|eval| eval.kind == Move::Init));
+    assert!(result.log.iter().any(|eval| eval.kind == Move::Reflection));
+}
+
+#[test]
+fn minimize_surrogate() {
+    let f = |x: &[f64; 3]| {
+        (x[0] - 1.0).powi(2) + 2.0 * (x[1] + 0.5).powi(2) + 3.0 * (x[0] + x[2]).powi(2)
+    };
+    let expected = 0.0;
+    let plain = Minimizer::<[f64; 3]>::default()
+        .minimize(&[1.0, 1.0, 1.0], f)
+        .unwrap();
+    let result = Minimizer::<[f64; 3]>::default()
+        .surrogate(5)
+        .minimize(&[1.0, 1.0, 1.0], f)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!("  fev = {:?} (plain {:?})", result.fev, plain.fev);
+
+    assert!((result.f_min - expected).abs() < 1e-9);
+    assert!(result.fev < plain.fev);
+}
+
+#[test]
+fn minimize_polish() {
+    let minimizer = Minimizer::<[f64; 2]>::default().polish(Polish::default());
+    let expected = 0.0;
+    let result = minimizer
+        .minimize(&[-1.0, 1.0], |x| {
+            (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0].powi(2)).powi(2)
+        })
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!(" iter = {:?}", result.iter);
+    println!("  fev = {:?}", result.fev);
+
+    assert_eq!(result.phase, Phase::Polish);
+    assert!((result.f_min - expected).abs() < 1e-12);
+}
+
+#[test]
+fn minimize_shaped() {
+    let minimizer = Minimizer::<[f64; 2]>::default();
+    let expected = 0.0;
+    let cov = [1.0, 0.9, 0.9, 1.0];
+    let result = minimizer
+        .minimize_shaped(&[3.0, 2.0], Shape::Covariance(&cov), |x| {
+            (x[0] - 1.0).powi(2) + 10.0 * (x[0] - x[1]).powi(2)
+        })
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!(" iter = {:?}", result.iter);
+
+    assert!((result.f_min - expected).abs() < 1e-6);
+}
+
+#[test]
+fn minimize_presets() {
+    let f = |x: &[f64; 2]| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0].powi(2)).powi(2);
+    let expected = 0.0;
+
+    let fast = Minimizer::fast().minimize(&[-1.0, 1.0], f).unwrap();
+    let accurate = Minimizer::accurate().minimize(&[-1.0, 1.0], f).unwrap();
+
+    println!("fast     f_min = {:?}, fev = {:?}", fast.f_min, fast.fev);
+    println!(
+        "accurate f_min = {:?}, fev = {:?}",
+        accurate.f_min, accurate.fev
+    );
+
+    assert!((accurate.f_min - expected).abs() < 1e-12);
+    assert!(fast.fev < accurate.fev);
+}
+
+#[test]
+fn minimize_robust() {
+    // Deterministic noise
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut noise = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 11) as f64 / (1u64 << 53) as f64 - 0.5
+    };
+
+    let minimizer = Minimizer::<[f64; 2]>::robust();
+    let result = minimizer
+        .minimize(&[2.0, 2.0], |x| {
+            x[0].powi(2) + x[1].powi(2) + 1e-3 * noise()
+        })
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+
+    assert!(result.x_min.iter().all(|xi| xi.abs() < 0.1));
+}
+
+#[test]
+fn minimize_half() {
+    use half::bf16;
+    use half::f16;
+    use num_traits::NumCast;
+    use num_traits::ToPrimitive;
+
+    let minimizer = Minimizer::<[f16; 1]>::default();
+    let x0 = <f16 as NumCast>::from(1.0).unwrap();
+    let result = minimizer.minimize(&[x0], |x| x[0] * x[0]).unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+
+    assert!(result.f_min.to_f32().unwrap() < 1e-3);
+
+    let minimizer = Minimizer::<[bf16; 2]>::default();
+    let x0 = <bf16 as NumCast>::from(1.0).unwrap();
+    let result = minimizer
+        .minimize(&[x0, x0], |x| x[0] * x[0] + x[1] * x[1])
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+
+    assert!(result.f_min.to_f32().unwrap() < 1e-2);
+}
+
+#[test]
+fn differential_evolution_rastrigin() {
+    let rastrigin = |x: &[f64; 2]| {
+        x.iter()
+            .map(|xi| xi.powi(2) - 10.0 * (2.0 * std::f64::consts::PI * xi).cos() + 10.0)
+            .sum::<f64>()
+    };
+    let bounds = [(-5.12, 5.12); 2];
+    let expected = 0.0;
+
+    let result = DifferentialEvolution::default()
+        .seed(42)
+        .local(Minimizer::default())
+        .minimize(&bounds, rastrigin)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!(" iter = {:?}", result.iter);
+    println!("  fev = {:?}", result.fev);
+
+    assert_eq!(result.phase, Phase::NelderMead);
+    assert!((result.f_min - expected).abs() < 1e-6);
+
+    // The refinement evaluations are counted, even without improvement
+    let mut fev = 0;
+    let handle = BestSoFar::new();
+    let result = DifferentialEvolution::default()
+        .seed(42)
+        .local(Minimizer::default().max_fev(5))
+        .best_so_far(handle.clone())
+        .minimize(&bounds, |x| {
+            fev += 1;
+            rastrigin(x)
+        })
+        .unwrap();
+
+    assert_eq!(result.fev, fev);
+    assert_eq!(handle.fev(), result.fev);
+    assert_eq!(handle.get().unwrap().f, result.best_ever.f);
+
+    #[cfg(feature = "rayon")]
+    {
+        let sequential = DifferentialEvolution::default()
+            .seed(42)
+            .minimize(&bounds, rastrigin)
+            .unwrap();
+        let parallel = DifferentialEvolution::default()
+            .seed(42)
+            .minimize_par(&bounds, rastrigin)
+            .unwrap();
+
+        assert_eq!(parallel.x_min, sequential.x_min);
+        assert_eq!(parallel.fev, sequential.fev);
+    }
+}
+
+#[test]
+fn cma_es_ellipsoid() {
+    let ellipsoid = |x: &[f64; 10]| {
+        x.iter()
+            .enumerate()
+            .map(|(i, xi)| 1e6f64.powf(i as f64 / 9.0) * (xi - 1.0).powi(2))
+            .sum::<f64>()
+    };
+    let expected = 0.0;
+
+    let result = CmaEs::default()
+        .sigma(0.5)
+        .seed(7)
+        .minimize(&[0.0; 10], ellipsoid)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!(" iter = {:?}", result.iter);
+    println!("  fev = {:?}", result.fev);
+
+    assert_eq!(result.phase, Phase::CmaEs);
+    assert!((result.f_min - expected).abs() < 1e-8);
+
+    let handle = BestSoFar::new();
+    let shared = CmaEs::default()
+        .sigma(0.5)
+        .seed(7)
+        .best_so_far(handle.clone())
+        .minimize(&[0.0; 10], ellipsoid)
+        .unwrap();
+
+    assert_eq!(shared.x_min, result.x_min);
+    assert_eq!(handle.fev(), shared.fev);
+    assert_eq!(handle.get().unwrap().f, shared.best_ever.f);
+}
+
+#[test]
+fn particle_swarm_ackley() {
+    let ackley = |x: &[f64; 2]| {
+        let tau = 2.0 * std::f64::consts::PI;
+        let squares = 0.5 * (x[0].powi(2) + x[1].powi(2));
+        let cosines = 0.5 * ((tau * x[0]).cos() + (tau * x[1]).cos());
+        -20.0 * (-0.2 * squares.sqrt()).exp() - cosines.exp() + std::f64::consts::E + 20.0
+    };
+    let bounds = [(-5.0, 5.0); 2];
+    let expected = [0.0; 2];
+
+    let result = ParticleSwarm::default()
+        .seed(3)
+        .minimize(&bounds, ackley)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!(" iter = {:?}", result.iter);
+    println!("  fev = {:?}", result.fev);
+
+    assert_eq!(result.phase, Phase::ParticleSwarm);
+    for (x, e) in result.x_min.iter().zip(&expected) {
+        assert!((x - e).abs() < 1e-3);
+    }
+
+    // The handle sees the refinement too
+    let handle = BestSoFar::new();
+    let refined = ParticleSwarm::default()
+        .seed(3)
+        .local(Minimizer::default())
+        .best_so_far(handle.clone())
+        .minimize(&bounds, ackley)
+        .unwrap();
+
+    assert_eq!(handle.fev(), refined.fev);
+    assert_eq!(handle.get().unwrap().f, refined.best_ever.f);
+
+    #[cfg(feature = "rayon")]
+    {
+        let parallel = ParticleSwarm::default()
+            .seed(3)
+            .minimize_par(&bounds, ackley)
+            .unwrap();
+
+        assert_eq!(parallel.x_min, result.x_min);
+        assert_eq!(parallel.fev, result.fev);
+    }
+}
+
+#[test]
+fn direct_branin() {
+    let branin = |x: &[f64; 2]| {
+        let pi = std::f64::consts::PI;
+        let b = 5.1 / (4.0 * pi * pi);
+        let c = 5.0 / pi;
+        let t = 1.0 / (8.0 * pi);
+        (x[1] - b * x[0].powi(2) + c * x[0] - 6.0).powi(2) + 10.0 * (1.0 - t) * x[0].cos() + 10.0
+    };
+    let bounds = [(-5.0, 10.0), (0.0, 15.0)];
+    let expected = 0.397887;
+
+    let result = Direct::default().minimize(&bounds, branin).unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!(" iter = {:?}", result.iter);
+    println!("  fev = {:?}", result.fev);
+
+    assert_eq!(result.phase, Phase::Direct);
+    assert!((result.f_min - expected).abs() < 1e-4);
+
+    let polished = Direct::default()
+        .tol_x(1e-2)
+        .local(Minimizer::default())
+        .minimize(&bounds, branin)
+        .unwrap();
+
+    println!("f_min = {:?}", polished.f_min);
+    println!("  fev = {:?}", polished.fev);
+
+    assert_eq!(polished.phase, Phase::NelderMead);
+    assert!((polished.f_min - expected).abs() < 1e-6);
+}
+
+#[test]
+fn spsa_noisy_sphere() {
+    let mut state = 1u64;
+    let noisy = move |x: &[f64; 10]| {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+        let noise = ((state >> 11) as f64 / (1u64 << 53) as f64 - 0.5) * 1e-2;
+        x.iter().map(|xi| xi.powi(2)).sum::<f64>() + noise
+    };
+
+    let result = Spsa::default().seed(1).minimize(&[1.0; 10], noisy).unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!("  fev = {:?}", result.fev);
+
+    assert_eq!(result.phase, Phase::Spsa);
+    assert_eq!(result.termination, Termination::Iterations);
+    assert_eq!(result.fev, 2001);
+    for x in result.x_min {
+        assert!(x.abs() < 0.1);
+    }
+}
+
+#[test]
+fn random_search() {
+    let himmelblau =
+        |x: &[f64; 2]| (x[0].powi(2) + x[1] - 11.0).powi(2) + (x[0] + x[1].powi(2) - 7.0).powi(2);
+    let bounds = [(-5.0, 5.0); 2];
+
+    let result = RandomSearch::default()
+        .seed(7)
+        .minimize(&bounds, himmelblau)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+
+    assert_eq!(result.phase, Phase::RandomSearch);
+    assert_eq!(result.fev, 200);
+    assert!(result.f_min < 5.0);
+
+    let refined = RandomSearch::default()
+        .seed(7)
+        .local(Minimizer::default())
+        .minimize(&bounds, himmelblau)
+        .unwrap();
+
+    println!("f_min = {:?}", refined.f_min);
+
+    assert!(refined.f_min < 1e-6);
+    assert!(refined.fev > result.fev);
+
+    let handle = BestSoFar::new();
+    let shared = RandomSearch::default()
+        .seed(7)
+        .local(Minimizer::default())
+        .best_so_far(handle.clone())
+        .minimize(&bounds, himmelblau)
+        .unwrap();
+
+    assert_eq!(shared.x_min, refined.x_min);
+    assert_eq!(handle.fev(), shared.fev);
+    assert_eq!(handle.get().unwrap().f, shared.best_ever.f);
+}
+
+#[test]
+fn bayesian_branin() {
+    let branin = |x: &[f64; 2]| {
+        let pi = std::f64::consts::PI;
+        let b = 5.1 / (4.0 * pi * pi);
+        let c = 5.0 / pi;
+        let t = 1.0 / (8.0 * pi);
+        (x[1] - b * x[0].powi(2) + c * x[0] - 6.0).powi(2) + 10.0 * (1.0 - t) * x[0].cos() + 10.0
+    };
+    let bounds = [(-5.0, 10.0), (0.0, 15.0)];
+
+    let result = BayesianOptimization::default()
+        .minimize(&bounds, branin)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+
+    assert_eq!(result.phase, Phase::BayesianOptimization);
+    assert_eq!(result.fev, 40);
+    assert!(result.f_min < 0.5);
+}
+
+#[test]
+fn timing() {
+    let slow = |x: &[f64; 2]| {
+        std::thread::sleep(std::time::Duration::from_micros(50));
+        x.iter().map(|xi| xi.powi(2)).sum::<f64>()
+    };
+
+    let result = Minimizer::default()
+        .timing(true)
+        .minimize(&[1.0, 1.0], slow)
+        .unwrap();
+    let timing = result.timing.unwrap();
+
+    println!("timing = {:?}", timing);
+    println!("   fev = {:?}", result.fev);
+
+    assert!(timing.min >= std::time::Duration::from_micros(50));
+    assert!(timing.min <= timing.mean && timing.mean <= timing.max);
+    assert!(timing.total >= timing.max);
+    assert!(Minimizer::default()
+        .minimize(&[1.0, 1.0], slow)
+        .unwrap()
+        .timing
+        .is_none());
+}
+
+#[test]
+fn catch_panics() {
+    let fragile = |x: &[f64; 2]| {
+        assert!(x[0] < 1.5, "out of the model domain");
+        (x[0] - 1.0).powi(2) + (x[1] - 1.0).powi(2)
+    };
+    let expected = [1.0; 2];
+
+    let result = Minimizer::default()
+        .catch_panics(PanicPolicy::Infeasible)
+        .minimize(&[1.49, 0.0], fragile)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+
+    for (x, e) in result.x_min.iter().zip(&expected) {
+        assert!((x - e).abs() < 1e-2);
+    }
+
+    let result = Minimizer::default()
+        .catch_panics(PanicPolicy::Fail)
+        .minimize(&[1.49, 0.0], fragile);
+
+    match result {
+        Err(Error::Panicked { x, message }) => {
+            assert!(x[0] >= 1.5);
+            assert_eq!(message, "out of the model domain");
+        }
+        _ => panic!("expected a panic error"),
+    }
+}
+
+#[test]
+fn best_so_far() {
+    let handle = BestSoFar::new();
+    let polled = handle.clone();
+    let mut last = f64::INFINITY;
+    let sphere = |x: &[f64; 2]| {
+        if let Some(best) = polled.get() {
+            assert!(best.f <= last);
+            last = best.f;
+        }
+        x.iter().map(|xi| xi.powi(2)).sum::<f64>()
+    };
+
+    let result = Minimizer::default()
+        .best_so_far(handle.clone())
+        .minimize(&[1.0, -2.0], sphere)
+        .unwrap();
+    let best = handle.get().unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!(" best = {:?}", best);
+
+    assert_eq!(best.f, result.best_ever.f);
+    assert_eq!(handle.fev(), result.fev);
+
+    // Shared by the starts, the best seed going first
+    let handle = BestSoFar::new();
+    let seeds = [[0.0, 0.0], [10.0, -10.0]];
+    let result = Minimizer::default()
+        .best_so_far(handle.clone())
+        .minimize_multistart(&seeds, |x: &[f64; 2]| x.iter().map(|xi| xi.powi(2)).sum());
+    let best = handle.get().unwrap();
+
+    assert_eq!(best.f, 0.0);
+    assert_eq!(best.x, [0.0, 0.0]);
+    assert_eq!(handle.fev(), result.fev.iter().sum::<usize>());
+}
+
+#[test]
+fn minimize_ordered() {
+    // Feasibility first, then the function value
+    #[derive(Debug, Clone, PartialEq, PartialOrd)]
+    struct Value {
+        violation: f64,
+        f: f64,
+    }
+
+    impl ObjectiveValue<f64> for Value {
+        fn scalar(&self) -> f64 {
+            self.f
+        }
+
+        fn infeasible() -> Value {
+            Value {
+                violation: f64::INFINITY,
+                f: f64::INFINITY,
+            }
+        }
+    }
+
+    // Subject to x + y <= 2
+    let constrained = |x: &[f64; 2]| Value {
+        violation: (x[0] + x[1] - 2.0).max(0.0),
+        f: (x[0] - 2.0).powi(2) + (x[1] - 2.0).powi(2),
+    };
+    let expected = [1.0; 2];
+
+    let result = Minimizer::default()
+        .minimize_ordered(&[0.0, 0.5], constrained)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!(" iter = {:?}", result.iter);
+
+    assert_eq!(result.f_min.violation, 0.0);
+    for (x, e) in result.x_min.iter().zip(&expected) {
+        assert!((x - e).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn minimize_lexicographic() {
+    // Any error below 0.25 is acceptable, then the smallest norm is preferred
+    let acceptable = |x: &[f64; 2]| {
+        let error = ((x[0] - 1.0).powi(2) - 0.25).max(0.0);
+        let norm = x[0].powi(2) + x[1].powi(2);
+        (error, norm)
+    };
+    let expected = [0.5, 0.0];
+
+    let result = Minimizer::default()
+        .minimize_lexicographic(&[2.0, 1.0], acceptable)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!(" iter = {:?}", result.iter);
+
+    assert_eq!(result.f_min.primary, 0.0);
+    assert!((result.f_min.secondary - 0.25).abs() < 1e-3);
+    for (x, e) in result.x_min.iter().zip(&expected) {
+        assert!((x - e).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn max_step() {
+    let sphere = |x: &[f64; 2]| x.iter().map(|xi| (xi - 10.0).powi(2)).sum::<f64>();
+    let max_step = 0.5;
+    let expected = [10.0; 2];
+
+    let result = Minimizer::default()
+        .max_step(max_step)
+        .record(true)
+        .minimize(&[1.0, 1.0], sphere)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!(" iter = {:?}", result.iter);
+
+    // The expansion point lies on the ray of the previous reflection point
+    for pair in result.log.windows(2) {
+        if pair[1].kind == Move::Expansion {
+            let dist = (pair[1].x[0] - pair[0].x[0]).hypot(pair[1].x[1] - pair[0].x[1]);
+            assert!(dist <= max_step + 1e-12);
+        }
+    }
+    for (x, e) in result.x_min.iter().zip(&expected) {
+        assert!((x - e).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn max_consecutive_shrinks() {
+    // Noise far above the function variations
+    let mut state = 1u64;
+    let noisy = |x: &[f64; 2]| {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+        let noise = (state >> 11) as f64 / (1u64 << 53) as f64;
+        1e-6 * (x[0].powi(2) + x[1].powi(2)) + noise
+    };
+
+    let result = Minimizer::default()
+        .max_consecutive_shrinks(3, ShrinkReaction::Terminate)
+        .minimize(&[1.0, 1.0], noisy)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!(" iter = {:?}", result.iter);
+
+    assert_eq!(result.termination, Termination::Shrinks);
+}
+
+#[test]
+fn flat_objective() {
+    // Bug: the function ignores its inputs
+    let constant = |_: &[f64; 3]| 42.0;
+
+    let result = Minimizer::default()
+        .flat_iter(5)
+        .minimize(&[1.0, 2.0, 3.0], constant)
+        .unwrap();
+
+    println!(" iter = {:?}", result.iter);
+
+    assert_eq!(result.termination, Termination::FlatObjective);
+    assert_eq!(result.iter, 4);
+}
+
+#[test]
+fn minimize_subset() {
+    let base: Vec<f64> = (0..1000).map(|idx| idx as f64).collect();
+    let indices = [3, 500, 998];
+    let expected = [1.0, 2.0, 3.0];
+
+    // Only the selected coordinates can move away from the base
+    let shifted =
+        |x: &[f64]| (x[3] - 1.0).powi(2) + (x[500] - 2.0).powi(2) + (x[998] - 3.0).powi(2) + x[999];
+
+    let result: Output<Vec<f64>> = Minimizer::default()
+        .minimize_subset(&base, &indices, shifted)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+
+    assert_eq!(result.x_min.len(), indices.len());
+    assert!((result.f_min - 999.0).abs() < 1e-4);
+    for (x, e) in result.x_min.iter().zip(&expected) {
+        assert!((x - e).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn minimize_typed() {
+    #[derive(Debug, Clone, Copy)]
+    struct Meters(f64);
+
+    #[derive(Debug, Clone, Copy)]
+    struct Seconds(f64);
+
+    struct Launch {
+        height: Meters,
+        delay: Seconds,
+    }
+
+    impl Parameters<f64> for Launch {
+        fn to_scalars(&self) -> Vec<f64> {
+            vec![self.height.0, self.delay.0]
+        }
+
+        fn from_scalars(x: &[f64]) -> Launch {
+            Launch {
+                height: Meters(x[0]),
+                delay: Seconds(x[1]),
+            }
+        }
+    }
+
+    let cost = |launch: &Launch| {
+        let Meters(height) = launch.height;
+        let Seconds(delay) = launch.delay;
+        (height - 120.0).powi(2) + (delay - 4.0).powi(2)
+    };
+    let seed = Launch {
+        height: Meters(100.0),
+        delay: Seconds(1.0),
+    };
+
+    let result: Output<[f64; 2]> = Minimizer::default().minimize_typed(&seed, cost).unwrap();
+    let best: Launch = Parameters::from_scalars(&result.x_min);
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+
+    assert!((best.height.0 - 120.0).abs() < 1e-2);
+    assert!((best.delay.0 - 4.0).abs() < 1e-2);
+}
+
+#[test]
+fn minimize_aux() {
+    // Residual breakdown of a least squares fit
+    let data = [(0.0, 1.0), (1.0, 3.0), (2.0, 5.0)];
+    let residuals = |x: &[f64; 2]| {
+        let breakdown: Vec<f64> = data.iter().map(|(t, y)| x[0] * t + x[1] - y).collect();
+        let f = breakdown.iter().map(|r| r.powi(2)).sum::<f64>();
+        (f, breakdown)
+    };
+
+    let result = Minimizer::default()
+        .minimize_aux(&[1.0, 0.0], residuals)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+
+    let aux = &result.f_min.aux;
+    assert_eq!(aux.len(), data.len());
+    assert!((aux.iter().map(|r| r.powi(2)).sum::<f64>() - result.f_min.f).abs() < 1e-12);
+    assert!(aux.iter().all(|r| r.abs() < 1e-2));
+}
+
+#[test]
+fn minimize_multistart() {
+    // Two basins, the deepest one around 2
+    let double_well = |x: &[f64; 1]| (x[0].powi(2) - 4.0).powi(2) - x[0];
+    let seeds = [[-3.0], [-1.0], [1.0], [3.0], [0.5], [-0.5]];
+    let max_fev = 200;
+
+    let result = Minimizer::default()
+        .max_fev(max_fev)
+        .minimize_multistart(&seeds, double_well);
+    let best = result.best.unwrap();
+
+    println!("f_min = {:?}", best.f_min);
+    println!("x_min = {:?}", best.x_min);
+    println!("  fev = {:?}", result.fev);
+
+    assert!(result.fev.len() < seeds.len());
+    assert!(result.fev.iter().sum::<usize>() <= max_fev);
+    assert!((best.x_min[0] - 2.0).abs() < 1e-1);
+}
+
+#[test]
+fn minimize_multistart_exhausted() {
+    use std::thread;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    let sphere = |x: &[f64; 2]| x.iter().map(|xi| xi.powi(2)).sum::<f64>();
+    let seeds = [[3.0, -2.0], [1.0, 1.0]];
+
+    // No start converges, the best point is still kept
+    let result = Minimizer::default()
+        .max_fev(10)
+        .minimize_multistart(&seeds, sphere);
+
+    println!("best_ever = {:?}", result.best_ever);
+
+    assert!(result.best.is_none());
+    let best_ever = result.best_ever.unwrap();
+    assert!(best_ever.f < sphere(&seeds[0]));
+    assert_eq!(best_ever.f, sphere(&best_ever.x));
+
+    // The time limit bounds the running start
+    let max_time = Duration::from_millis(50);
+    let started = Instant::now();
+    let mut count = 0;
+    let result =
+        Minimizer::default()
+            .max_time(max_time)
+            .minimize_multistart(&seeds, |x: &[f64; 2]| {
+                // Noisy, so that no start converges
+                thread::sleep(Duration::from_millis(1));
+                count += 1;
+                sphere(x) + (count % 7) as f64
+            });
+
+    println!("     time = {:?}", result.time);
+
+    assert!(started.elapsed() < 4 * max_time);
+    assert_eq!(result.time.len(), 1);
+    assert!(result.best.is_none());
+    assert!(result.best_ever.is_some());
+}
+
+#[test]
+fn minimize_n() {
+    let booth =
+        |x: &[f64; 2]| (x[0] + 2.0 * x[1] - 7.0).powi(2) + (2.0 * x[0] + x[1] - 5.0).powi(2);
+    let expected = [1.0, 3.0];
+
+    let result = Minimizer::default().minimize_n(&[0.0; 2], booth).unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+
+    for (x, e) in result.x_min.iter().zip(&expected) {
+        assert!((x - e).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn minimize_owned() {
+    let himmelblau =
+        |x: &Vec<f64>| (x[0].powi(2) + x[1] - 11.0).powi(2) + (x[0] + x[1].powi(2) - 7.0).powi(2);
+    let expected = [3.0, 2.0];
+
+    let result = Minimizer::default()
+        .record(true)
+        .minimize_owned(vec![2.0, 2.5], himmelblau)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+
+    assert_eq!(result.log[0].x, vec![2.0, 2.5]);
+    for (x, e) in result.x_min.iter().zip(&expected) {
+        assert!((x - e).abs() < 1e-2);
+    }
+}
+
+#[test]
+fn tol_x_each() {
+    // Meters and radians
+    let mixed = |x: &[f64; 2]| (x[0] / 1000.0 - 2.0).powi(2) + (x[1] - 0.5).powi(2);
+    let tol_x = [1e-1, 1e-6];
+
+    let result = Minimizer::default()
+        .tol_x_each(&tol_x)
+        .minimize(&[1000.0, 0.1], mixed)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+
+    assert!((result.x_min[0] - 2000.0).abs() < 1.0);
+    assert!((result.x_min[1] - 0.5).abs() < 1e-3);
+}
+
+#[test]
+fn minimize_with_f0() {
+    let sphere = |x: &[f64; 3]| x.iter().map(|xi| (xi - 1.0).powi(2)).sum::<f64>();
+    let x0 = [0.0; 3];
+
+    let fresh = Minimizer::default().minimize(&x0, sphere).unwrap();
+    let known = Minimizer::default()
+        .minimize_with_f0(&x0, sphere(&x0), sphere)
+        .unwrap();
+
+    println!("f_min = {:?}", known.f_min);
+    println!("  fev = {:?}", known.fev);
+
+    assert_eq!(known.fev + 1, fresh.fev);
+    assert_eq!(known.x_min, fresh.x_min);
+
+    // The seed is tracked, but not counted
+    let handle = BestSoFar::new();
+    let tracked = Minimizer::default()
+        .trajectory(true)
+        .best_so_far(handle.clone())
+        .minimize_with_f0(&x0, sphere(&x0), sphere)
+        .unwrap();
+
+    assert_eq!(tracked.trajectory[0].x, x0);
+    assert_eq!(tracked.trajectory[0].f, sphere(&x0));
+    assert_eq!(tracked.fev, known.fev);
+    assert_eq!(handle.fev(), known.fev);
+    assert_eq!(handle.get().unwrap().x, tracked.best_ever.x);
+}
+
+#[test]
+fn fit() {
+    let quadratic =
+        |x: &[f64; 2]| (x[0] - 1.0).powi(2) + 2.0 * (x[1] + 0.5).powi(2) + 0.5 * x[0] * x[1];
+    // Solution of the gradient equations
+    let expected = [36.0 / 31.0, -20.0 / 31.0];
+
+    let result = Minimizer::default()
+        .fit(true)
+        .minimize(&[0.0, 0.0], quadratic)
+        .unwrap();
+    let fitted = result.fitted.unwrap();
+
+    println!("x_min = {:?}", result.x_min);
+    println!("fitted = {:?}", fitted);
+
+    let error = |x: &[f64; 2]| (x[0] - expected[0]).hypot(x[1] - expected[1]);
+    assert!(error(&fitted.x) < 1e-8);
+    assert!(error(&fitted.x) < error(&result.x_min));
+    assert!((fitted.f - quadratic(&expected)).abs() < 1e-10);
+}
+
+#[test]
+fn minimize_in() {
+    let minimizer = Minimizer::default();
+    let mut workspace = Workspace::new();
+
+    // Fits shifted parabolas, as a per-frame estimation would
+    for shift in 0..10 {
+        let shift = f64::from(shift);
+        let parabola = |x: &Vec<f64>| (x[0] - shift).powi(2) + (x[1] + shift).powi(2);
+        let expected = minimizer.minimize(&[0.0, 0.0], parabola).unwrap();
+        let result = minimizer
+            .minimize_in(&mut workspace, &[0.0, 0.0], parabola)
+            .unwrap();
+
+        assert_eq!(result.x_min, expected.x_min);
+        assert_eq!(result.fev, expected.fev);
+        assert!((result.x_min[0] - shift).abs() < 1e-2);
+        assert!((result.x_min[1] + shift).abs() < 1e-2);
+    }
+
+    // The batch storage is kept along, whatever the steps
+    let minimizer = minimizer.speculative(true);
+    let rosenbrock = |x: &Vec<f64>| (1.0 - x[0]).powi(2) + 100.0 * (x[1] - x[0].powi(2)).powi(2);
+    for _ in 0..2 {
+        let expected = minimizer.minimize(&[-1.2, 1.0], rosenbrock).unwrap();
+        let result = minimizer
+            .minimize_in(&mut workspace, &[-1.2, 1.0], rosenbrock)
+            .unwrap();
+
+        assert_eq!(result.x_min, expected.x_min);
+        assert_eq!(result.fev, expected.fev);
+    }
+}
+
+#[test]
+fn cobyla() {
+    // Linear function on the unit disk, and a parabola cut by a half-plane
+    let linear = |x: &[f64; 2]| x[0] + x[1];
+    let disk = |x: &[f64; 2]| vec![1.0 - x[0].powi(2) - x[1].powi(2)];
+    let parabola = |x: &[f64; 2]| (x[0] - 2.0).powi(2) + (x[1] - 1.0).powi(2);
+    let half_plane = |x: &[f64; 2]| vec![2.0 - x[0] - x[1]];
+
+    let result = Cobyla::default()
+        .minimize(&[0.0, 0.0], linear, disk)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!("fev = {:?}", result.fev);
+
+    assert_eq!(result.phase, Phase::Cobyla);
+    for x in result.x_min {
+        assert!((x + 0.5f64.sqrt()).abs() < 1e-4);
+    }
+
+    let result = Cobyla::default()
+        .minimize(&[0.0, 0.0], parabola, half_plane)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!("fev = {:?}", result.fev);
+
+    for (x, e) in result.x_min.iter().zip(&[1.5, 0.5]) {
+        assert!((x - e).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn optimizer() {
+    // Minimum on the bounds
+    let sphere = |x: &[f64; 2]| (x[0] - 2.0).powi(2) + (x[1] + 0.5).powi(2);
+    let bounds = [(-1.0, 1.0); 2];
+    let expected = [1.0, -0.5];
+
+    let methods: Vec<Method<[f64; 2]>> = vec![
+        Minimizer::default().into(),
+        Cobyla::default().into(),
+        CmaEs::default().into(),
+        DifferentialEvolution::default().into(),
+        ParticleSwarm::default().into(),
+        Direct::default().into(),
+    ];
+
+    for method in methods {
+        let global = method.is_global();
+        let result = Optimizer::new(method)
+            .bounds(&bounds)
+            .minimize(&[0.0, 0.0], sphere)
+            .unwrap();
+
+        println!("global = {:?}", global);
+        println!("x_min = {:?}", result.x_min);
+
+        for (x, e) in result.x_min.iter().zip(&expected) {
+            assert!((x - e).abs() < 1e-2);
+        }
+        assert_eq!(result.f_min, sphere(&result.x_min));
+    }
+
+    // Global methods fail without bounds
+    let result = Optimizer::new(DifferentialEvolution::default()).minimize(&[0.0, 0.0], sphere);
+    assert!(matches!(result, Err(Error::MissingBounds)));
+
+    // Same budget and handle, whatever the method, Nelder-Mead
+    // checking the limit once per iteration and the others
+    // before each evaluation
+    let methods: [(Method<[f64; 2]>, usize); 3] = [
+        (Minimizer::default().into(), 3),
+        (Direct::default().into(), 0),
+        (Cobyla::default().into(), 0),
+    ];
+    for (method, slack) in methods {
+        let handle = BestSoFar::new();
+        let result = Optimizer::new(method)
+            .bounds(&bounds)
+            .max_fev(20)
+            .best_so_far(handle.clone())
+            .minimize(&[0.0, 0.0], sphere);
+
+        assert!(matches!(result, Err(Error::MaxFev(20))));
+        assert!((20..=20 + slack).contains(&handle.fev()));
+        let best = handle.get().unwrap();
+        assert!(best.x.iter().all(|xi| (-1.0..=1.0).contains(xi)));
+        assert_eq!(best.f, sphere(&best.x));
+    }
+
+    // The initial samples and the refinement are within the budget,
+    // a Nelder-Mead iteration not being cut short
+    let methods: [(Method<[f64; 2]>, usize); 3] = [
+        (BayesianOptimization::default().into(), 0),
+        (RandomSearch::default().into(), 0),
+        (
+            RandomSearch::default()
+                .samples(MaxIter::Absolute(10))
+                .local(Minimizer::default())
+                .into(),
+            3,
+        ),
+    ];
+    for (method, slack) in methods {
+        let handle = BestSoFar::new();
+        let result = Optimizer::new(method)
+            .bounds(&bounds)
+            .max_fev(20)
+            .best_so_far(handle.clone())
+            .minimize(&[0.0, 0.0], sphere)
+            .unwrap();
+
+        assert_eq!(handle.fev(), result.fev);
+        assert!((20..=20 + slack).contains(&result.fev));
+    }
+}
+
+#[test]
+fn minimize_staged() {
+    use half::f16;
+
+    let rosenbrock = |x: &[f64; 2]| 100.0 * (x[1] - x[0] * x[0]).powi(2) + (1.0 - x[0]).powi(2);
+    let rosenbrock_f32 = |x: &[f32; 2]| 100.0 * (x[1] - x[0] * x[0]).powi(2) + (1.0 - x[0]).powi(2);
+
+    let minimizer = Minimizer::<[f64; 2]>::accurate();
+    let result = minimizer
+        .minimize_staged(&[-1.2, 1.0], rosenbrock_f32, rosenbrock)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!("fev = {:?}", result.fev);
+
+    assert!(result.f_min < 1e-12);
+    for x in result.x_min {
+        assert!((x - 1.0).abs() < 1e-5);
+    }
+
+    // From half precision up to double precision
+    let sphere_f16 = |x: &[f16; 2]| {
+        let one = <f16 as num_traits::NumCast>::from(1.0).unwrap();
+        (x[0] - one) * (x[0] - one) + (x[1] + one) * (x[1] + one)
+    };
+    let sphere_f32 = |x: &[f32; 2]| (x[0] - 1.0).powi(2) + (x[1] + 1.0).powi(2);
+    let sphere = |x: &[f64; 2]| (x[0] - 1.0).powi(2) + (x[1] + 1.0).powi(2);
+
+    let coarse = Minimizer::<[f32; 2]>::default()
+        .minimize_staged(&[0.0, 0.0], sphere_f16, sphere_f32)
+        .unwrap();
+    let result = Minimizer::<[f64; 2]>::accurate()
+        .refine(&coarse, sphere)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+
+    assert!(result.fev > coarse.fev);
+    assert!(result.f_min < 1e-14);
+}
+
+#[test]
+fn replay() {
+    let rosenbrock = |x: &[f64; 2]| 100.0 * (x[1] - x[0] * x[0]).powi(2) + (1.0 - x[0]).powi(2);
+
+    let minimizer = Minimizer::<[f64; 2]>::default().record(true);
+    let recorded = minimizer.minimize(&[-1.2, 1.0], rosenbrock).unwrap();
+
+    // One accepted move per iteration at least, with the initial vertices
+    let accepted = recorded.log.iter().filter(|eval| eval.accepted).count();
+    println!("accepted = {:?}", accepted);
+    assert!(accepted >= recorded.iter + 3);
+    assert!(recorded
+        .log
+        .iter()
+        .filter(|eval| eval.kind == Move::Init)
+        .all(|eval| eval.accepted));
+
+    let replayed = minimizer.replay(&[-1.2, 1.0], &recorded.log).unwrap();
+
+    println!("f_min = {:?}", replayed.f_min);
+    println!("x_min = {:?}", replayed.x_min);
+
+    assert_eq!(replayed.x_min, recorded.x_min);
+    assert_eq!(replayed.fev, recorded.fev);
+    for (a, b) in replayed.log.iter().zip(&recorded.log) {
+        assert_eq!(a.accepted, b.accepted);
+    }
+}
+
+#[cfg(feature = "indicatif")]
+#[test]
+fn progress_bar() {
+    use crate::progress::progress;
+
+    let sphere = |x: &[f64; 2]| x[0] * x[0] + x[1] * x[1];
+
+    let minimizer = Minimizer::<[f64; 2]>::default().max_fev(1000);
+    let bar = minimizer.progress_bar();
+    let result = minimizer
+        .minimize(&[1.0, 1.0], progress(&bar, sphere))
+        .unwrap();
+    bar.finish();
+
+    println!("message = {:?}", bar.message());
+
+    assert_eq!(bar.length(), Some(1000));
+    assert_eq!(bar.position(), result.fev as u64);
+    assert_eq!(bar.message(), format!("f = {}", result.best_ever.f));
+}
+
+#[test]
+fn portfolio() {
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::thread;
+    use std::time::Duration;
+
+    let rastrigin = |x: &[f64; 2]| {
+        x.iter()
+            .map(|&xi| xi * xi - 10.0 * (2.0 * std::f64::consts::PI * xi).cos() + 10.0)
+            .sum::<f64>()
+    };
+    let bounds = [(-5.12, 5.12); 2];
+
+    // The local solver gets stuck, the global one wins
+    let portfolio = Portfolio::default()
+        .solver(Optimizer::new(Minimizer::default()))
+        .solver(Optimizer::new(DifferentialEvolution::default()).bounds(&bounds));
+    let result = portfolio.minimize(&[3.1, -2.9], rastrigin).unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("phase = {:?}", result.phase);
+
+    assert_eq!(result.phase, Phase::DifferentialEvolution);
+    assert!(result.f_min < 1e-3);
+
+    // The first result cancels the other solver, the evaluations
+    // being slow enough for the local one to finish first
+    let (all, first) = (AtomicUsize::new(0), AtomicUsize::new(0));
+    portfolio
+        .minimize(&[3.1, -2.9], |x| {
+            all.fetch_add(1, Ordering::Relaxed);
+            thread::sleep(Duration::from_micros(20));
+            rastrigin(x)
+        })
+        .unwrap();
+    let result = portfolio
+        .first(true)
+        .minimize(&[3.1, -2.9], |x| {
+            first.fetch_add(1, Ordering::Relaxed);
+            thread::sleep(Duration::from_micros(20));
+            rastrigin(x)
+        })
+        .unwrap();
+
+    println!("phase = {:?}", result.phase);
+    let (all, first) = (all.into_inner(), first.into_inner());
+    println!("  fev = {:?}", (all, first));
+
+    assert_eq!(result.phase, Phase::NelderMead);
+    assert!(first < all);
+
+    // Shared budget, even for a solver catching panics
+    let result = Portfolio::default()
+        .solver(Optimizer::new(
+            Minimizer::accurate().catch_panics(PanicPolicy::Infeasible),
+        ))
+        .solver(Optimizer::new(CmaEs::default()))
+        .max_fev(20)
+        .minimize(&[3.1, -2.9], rastrigin);
+
+    assert!(matches!(result, Err(Error::MaxFev(20))));
+}
+
+#[test]
+fn trajectory() {
+    let rosenbrock = |x: &[f64; 2]| 100.0 * (x[1] - x[0] * x[0]).powi(2) + (1.0 - x[0]).powi(2);
+
+    let result = Minimizer::<[f64; 2]>::default()
+        .minimize(&[-1.2, 1.0], rosenbrock)
+        .unwrap();
+    assert!(result.trajectory.is_empty());
+
+    let result = Minimizer::<[f64; 2]>::default()
+        .trajectory(true)
+        .minimize(&[-1.2, 1.0], rosenbrock)
+        .unwrap();
+
+    println!("len = {:?}", result.trajectory.len());
+
+    assert_eq!(result.trajectory[0].x, [-1.2, 1.0]);
+    for pair in result.trajectory.windows(2) {
+        assert!(pair[1].f < pair[0].f);
+    }
+    let last = result.trajectory.last().unwrap();
+    assert_eq!(last.x, result.best_ever.x);
+}
+
+#[cfg(feature = "num-complex")]
+#[test]
+fn minimize_complex() {
+    use num_complex::Complex;
+
+    // Square root of 3 + 4i
+    let target = Complex::new(3.0, 4.0);
+    let residual = |z: &[Complex<f64>]| (z[0] * z[0] - target).norm().powi(2);
+
+    let minimizer = Minimizer::<[f64; 2]>::accurate();
+    let result = minimizer
+        .minimize_complex(&[Complex::new(1.0, 1.0)], residual)
+        .unwrap();
+    let z = Vec::<Complex<f64>>::from_scalars(&result.x_min);
+
+    println!("z = {:?}", z);
+
+    assert!((z[0] - Complex::new(2.0, 1.0)).norm() < 1e-6);
+}
+
+#[cfg(unix)]
+#[test]
+fn external() {
+    use std::time::Duration;
+
+    let expected = [1.0, -2.0];
+
+    // Parameters on the standard input
+    let command = External::new("awk").arg("{ printf \"%.17g\\n\", ($1 - 1)^2 + ($2 + 2)^2 }");
+    let result = Minimizer::<[f64; 2]>::default()
+        .minimize(&[0.0, 0.0], command.function())
+        .unwrap();
+
+    println!("x_min = {:?}", result.x_min);
+
+    for (x, e) in result.x_min.iter().zip(&expected) {
+        assert!((x - e).abs() < 1e-2);
+    }
+
+    // Parameters as arguments
+    let command = External::new("awk")
+        .arg("BEGIN { printf \"%.17g\\n\", (ARGV[1] - 1)^2 + (ARGV[2] + 2)^2 }")
+        .input(ExternalInput::Args);
+    assert_eq!(command.evaluate(&[1.0, -2.0]).unwrap(), 0.0);
+    assert_eq!(command.evaluate(&[2.0, -2.0]).unwrap(), 1.0);
+
+    // Failures
+    let command = External::new("sh").arg("-c").arg("echo oops >&2; exit 3");
+    match command.evaluate::<f64>(&[0.0]) {
+        Err(ExternalError::Status { status, stderr }) => {
+            assert_eq!(status.code(), Some(3));
+            assert_eq!(stderr.trim(), "oops");
+        }
+        _ => panic!("expected a status error"),
+    }
+
+    let command = External::new("sh")
+        .arg("-c")
+        .arg("sleep 5")
+        .timeout(Duration::from_millis(50));
+    assert!(matches!(
+        command.evaluate::<f64>(&[0.0]),
+        Err(ExternalError::Timeout(_))
+    ));
+
+    // A command ignoring an input larger than the pipe buffer
+    let large = vec![0.5; 100_000];
+    let command = External::new("sh").arg("-c").arg("echo 1");
+    assert_eq!(command.evaluate(&large).unwrap(), 1.0);
+
+    let started = std::time::Instant::now();
+    let command = External::new("sh")
+        .arg("-c")
+        .arg("sleep 5")
+        .timeout(Duration::from_millis(50));
+    assert!(matches!(
+        command.evaluate::<f64>(&large),
+        Err(ExternalError::Timeout(_))
+    ));
+    assert!(started.elapsed() < Duration::from_secs(2));
+
+    let command = External::new("sh")
+        .arg("-c")
+        .arg("cat > /dev/null; echo nan?");
+    let result = Minimizer::<[f64; 1]>::default()
+        .catch_panics(PanicPolicy::Fail)
+        .minimize(&[0.0], command.function());
+
+    match result {
+        Err(Error::Panicked { message, .. }) => {
+            assert_eq!(message, "Command output is not a value: \"nan?\"");
+        }
+        _ => panic!("expected a panic error"),
+    }
+}
+
+#[test]
+fn cache() {
+    use std::cell::Cell;
+
+    let calls = Cell::new(0);
+    let rosenbrock = |x: &[f64; 2]| {
+        calls.set(calls.get() + 1);
+        100.0 * (x[1] - x[0] * x[0]).powi(2) + (1.0 - x[0]).powi(2)
+    };
+    let path = std::env::temp_dir().join(format!("nelder-mead-cache-{}", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let minimizer = Minimizer::<[f64; 2]>::default();
+    let mut cache = Cache::open(&path).unwrap();
+    let first = minimizer
+        .minimize(&[-1.2, 1.0], cache.function(rosenbrock))
+        .unwrap();
+    assert_eq!(calls.get(), cache.len());
+
+    // Resumed from the file, without evaluating the function again
+    calls.set(0);
+    let mut cache = Cache::open(&path).unwrap();
+    let second = minimizer
+        .minimize(&[-1.2, 1.0], cache.function(rosenbrock))
+        .unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    println!("hits = {:?}", cache.hits());
+
+    assert_eq!(calls.get(), 0);
+    assert_eq!(cache.hits(), second.fev);
+    assert_eq!(first.x_min, second.x_min);
+
+    // Cut by a crash within the value of the last entry
+    std::fs::write(&path, "0.1 2.3 0.7\n1 2 0.7").unwrap();
+    let mut cache = Cache::open(&path).unwrap();
+    assert_eq!(cache.len(), 1);
+    let mut f = cache.function(|_: &[f64; 2]| 5.0);
+    assert_eq!(f(&[1.0, 2.0]), 5.0);
+    assert_eq!(f(&[0.1, 2.3]), 0.7);
+    drop(f);
+
+    let cache = Cache::<f64>::open(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(cache.len(), 2);
+    assert_eq!(cache.hits(), 0);
+}
+
+#[test]
+fn slices() {
+    let rosenbrock = |x: &[f64; 2]| 100.0 * (x[1] - x[0] * x[0]).powi(2) + (1.0 - x[0]).powi(2);
+
+    let result = Minimizer::<[f64; 2]>::accurate()
+        .minimize(&[-1.2, 1.0], rosenbrock)
+        .unwrap();
+
+    let slice = result.slice(0, 0.5, 11, rosenbrock);
+
+    println!("xs = {:?}", slice.xs);
+    println!("fs = {:?}", slice.fs);
+
+    assert_eq!(slice.xs.len(), 11);
+    assert!((slice.xs[0] - (result.x_min[0] - 0.5)).abs() < 1e-12);
+    assert!((slice.xs[10] - (result.x_min[0] + 0.5)).abs() < 1e-12);
+    let center = slice.fs[5];
+    assert!(slice.fs.iter().all(|&f| f >= center));
+
+    let slice = result.slice_2d((0, 1), (0.1, 0.2), 5, rosenbrock);
+    assert_eq!(slice.fs.len(), 25);
+    let (x, y) = (slice.xs[1], slice.ys[3]);
+    assert_eq!(slice.fs[5 + 3], rosenbrock(&[x, y]));
+    assert!(slice.fs.iter().all(|&f| f >= slice.fs[12]));
+}
+
+#[test]
+fn weights() {
+    // Mixture weights of three known components
+    let target = [0.2, 0.3, 0.5];
+    let distance = |w: &Weights<f64>| {
+        w.0.iter()
+            .zip(&target)
+            .map(|(wi, ti)| (wi - ti).powi(2))
+            .sum::<f64>()
+    };
+
+    let x0 = Weights(vec![1.0 / 3.0; 3]);
+    assert_eq!(x0.to_scalars(), vec![0.0, 0.0]);
+
+    let result = Minimizer::<[f64; 2]>::accurate()
+        .minimize_typed(&x0, distance)
+        .unwrap();
+    let weights: Weights<f64> = result.parameters();
+
+    println!("weights = {:?}", weights);
+
+    assert!((weights.0.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    for (w, t) in weights.0.iter().zip(&target) {
+        assert!((w - t).abs() < 1e-4);
+    }
+
+    // Round trip, and far points staying on the simplex
+    let back = Weights::<f64>::from_scalars(&weights.to_scalars());
+    for (a, b) in back.0.iter().zip(&weights.0) {
+        assert!((a - b).abs() < 1e-12);
+    }
+    let far = Weights::from_scalars(&[800.0, -800.0]);
+    assert_eq!(far.0, vec![1.0, 0.0, 0.0]);
+}
+
+#[test]
+fn strategy() {
+    // Reflection or inside contraction, without expansion
+    #[derive(Debug)]
+    struct Cautious;
+
+    impl Strategy<[f64; 2]> for Cautious {
+        fn step(
+            &self,
+            vertices: &[Point<[f64; 2]>],
+            evaluate: &mut dyn FnMut(&[f64; 2], Move) -> f64,
+        ) -> Step<f64> {
+            let worst = vertices.last().unwrap();
+            let mut centroid = [0.0; 2];
+            for vertex in &vertices[..vertices.len() - 1] {
+                for (c, x) in centroid.iter_mut().zip(&vertex.x) {
+                    *c += x / (vertices.len() - 1) as f64;
+                }
+            }
+            let towards = |t: f64| [0, 1].map(|i| centroid[i] + t * (centroid[i] - worst.x[i]));
+
+            if evaluate(&towards(1.0), Move::Reflection) < worst.f {
+                Step::Replace(0)
+            } else if evaluate(&towards(-0.5), Move::InsideContraction) < worst.f {
+                Step::Replace(1)
+            } else {
+                Step::Shrink(0.5)
+            }
+        }
+    }
+
+    let rosenbrock = |x: &[f64; 2]| 100.0 * (x[1] - x[0] * x[0]).powi(2) + (1.0 - x[0]).powi(2);
+
+    let result = Minimizer::<[f64; 2]>::default()
+        .max_iter(MaxIter::PerDimension(2000))
+        .strategy(Cautious)
+        .record(true)
+        .minimize(&[-1.2, 1.0], rosenbrock)
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+    println!("iter = {:?}", result.iter);
+
+    assert!(result.log.iter().all(|eval| eval.kind != Move::Expansion));
+    assert!(result
+        .log
+        .iter()
+        .any(|eval| eval.accepted && eval.kind == Move::InsideContraction));
+    for x in result.x_min {
+        assert!((x - 1.0).abs() < 1e-2);
+    }
+
+    // Replacing with a candidate never evaluated
+    #[derive(Debug)]
+    struct Careless;
+
+    impl Strategy<[f64; 2]> for Careless {
+        fn step(
+            &self,
+            _: &[Point<[f64; 2]>],
+            _: &mut dyn FnMut(&[f64; 2], Move) -> f64,
+        ) -> Step<f64> {
+            Step::Replace(0)
+        }
+    }
+
+    let minimizer = Minimizer::<[f64; 2]>::default().strategy(Careless);
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        minimizer.minimize(&[-1.2, 1.0], rosenbrock)
+    }));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert_eq!(
+        message,
+        "the strategy replaced the worst vertex with candidate 0 of 0"
+    );
+}
+
+#[test]
+fn convergence() {
+    // The second parameter barely matters
+    let sloppy = |x: &[f64; 2]| (x[0] - 1.0).powi(2) + 1e-20 * x[1].powi(2);
+
+    let minimizer = Minimizer::default();
+    let result = minimizer.minimize(&[0.0, 0.0], sloppy).unwrap();
+    let convergence = minimizer.convergence(&result);
+
+    println!("termination = {:?}", result.termination);
+    println!("convergence = {:?}", convergence);
+
+    assert!(convergence[0].converged);
+    assert!(convergence[0].ratio <= 1.0);
+    assert!(!convergence[1].converged);
+    assert!(convergence[1].ratio > 1.0);
+    assert_eq!(
+        convergence.iter().map(|c| c.index).collect::<Vec<_>>(),
+        [0, 1]
+    );
+
+    let each = Minimizer::default().tol_x_each(&[1e-2, 1e-6]);
+    let result = each.minimize(&[0.0, 0.0], |x: &[f64; 2]| {
+        x.iter().map(|xi| (xi - 1.0).powi(2)).sum()
+    });
+    let convergence: Vec<Convergence<f64>> = each.convergence(&result.unwrap());
+
+    assert_eq!(convergence[0].tol, 1e-2);
+    assert_eq!(convergence[1].tol, 1e-6);
+}
+
+#[test]
+fn minimize_tuned() {
+    let rosenbrock = |x: &[f64; 4]| {
+        x.windows(2)
+            .map(|w| 100.0 * (w[1] - w[0] * w[0]).powi(2) + (1.0 - w[0]).powi(2))
+            .sum::<f64>()
+    };
+
+    let mut count = 0;
+    let result = Minimizer::default()
+        .max_fev(5000)
+        .minimize_tuned(&[-1.0, 1.0, -1.0, 1.0], 0.2, |x: &[f64; 4]| {
+            count += 1;
+            rosenbrock(x)
+        })
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("fev = {:?}", result.fev);
+
+    assert!(result.f_min < 1e-4);
+    assert_eq!(result.fev, count);
+    assert!(result.fev <= 5000);
+}
+
+#[test]
+fn evaluator() {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::Context;
+    use std::task::Poll;
+
+    let rosenbrock = |x: &[f64; 3]| {
+        x.windows(2)
+            .map(|w| 100.0 * (w[1] - w[0] * w[0]).powi(2) + (1.0 - w[0]).powi(2))
+            .sum::<f64>()
+    };
+    let x0 = [-1.0, 1.0, 0.5];
+    let minimizer = Minimizer::default().max_iter(MaxIter::PerDimension(1000));
+    let reference = minimizer.minimize(&x0, rosenbrock).unwrap();
+
+    // Batches, the same points being evaluated in the same order
+    let mut batches = Vec::new();
+    let result = minimizer
+        .minimize_with(&x0, |xs: &[[f64; 3]]| {
+            batches.push(xs.len());
+            xs.iter().map(rosenbrock).collect()
+        })
+        .unwrap();
+
+    assert_eq!(batches[0], 4);
+    assert!(batches[1..].iter().all(|&len| len == 1 || len == 3));
+    assert_eq!(batches.iter().sum::<usize>(), result.fev);
+    assert_eq!(result.fev, reference.fev);
+    assert_eq!(result.x_min, reference.x_min);
+
+    // On threads
+    let result = minimizer
+        .minimize_with(&x0, Threads::new(rosenbrock).threads(2))
+        .unwrap();
+    assert_eq!(result.x_min, reference.x_min);
+
+    // Speculative candidates
+    let mut batches = Vec::new();
+    let result = minimizer
+        .clone()
+        .speculative(true)
+        .minimize_with(&x0, |xs: &[[f64; 3]]| {
+            batches.push(xs.len());
+            xs.iter().map(rosenbrock).collect()
+        })
+        .unwrap();
+
+    println!("f_min = {:?}", result.f_min);
+    println!("x_min = {:?}", result.x_min);
+
+    assert!(result.f_min < 1e-6);
+    assert!(batches[1..].iter().all(|&len| len == 3 || len == 4));
+
+    // Asynchronous, each batch being pending once
+    struct Pending(bool);
+
+    impl Future for Pending {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Pending>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                return Poll::Ready(());
+            }
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+
+    struct Remote;
+
+    impl AsyncEvaluator<[f64; 3]> for Remote {
+        async fn evaluate(&mut self, xs: &[[f64; 3]]) -> Vec<f64> {
+            Pending(false).await;
+            xs.iter()
+                .map(|x| {
+                    x.windows(2)
+                        .map(|w| 100.0 * (w[1] - w[0] * w[0]).powi(2) + (1.0 - w[0]).powi(2))
+                        .sum()
+                })
+                .collect()
+        }
+    }
+
+    let result = minimizer.minimize_async(&x0, Remote).unwrap();
+    assert_eq!(result.x_min, reference.x_min);
+
+    // Multiple starts
+    let seeds = [x0, [0.0; 3]];
+    let result = minimizer.minimize_multistart_with(&seeds, |xs: &[[f64; 3]]| {
+        xs.iter().map(rosenbrock).collect()
+    });
+    assert_eq!(result.fev[0], reference.fev);
+    assert!(result.best.unwrap().f_min <= reference.f_min);
+}
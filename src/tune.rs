@@ -0,0 +1,105 @@
+use array::Array;
+use num_traits::Float;
+use num_traits::NumCast;
+
+use crate::minimizer::Minimizer;
+use crate::minimizer::Result;
+
+/// Coefficient sets tried by the probe runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Coefficients {
+    /// Reflection 1, contraction 1/2, expansion 2 and shrinkage 1/2.
+    Standard,
+    /// Dimension-dependent coefficients of Gao and Han (2012).
+    Adaptive,
+    /// Standard coefficients, expanding three times as far as the reflection.
+    GreedyExpansion,
+}
+
+impl Coefficients {
+    const ALL: [Coefficients; 3] = [
+        Coefficients::Standard,
+        Coefficients::Adaptive,
+        Coefficients::GreedyExpansion,
+    ];
+
+    /// Gives `minimizer` with the coefficients set.
+    fn apply<A: Array>(self, mut minimizer: Minimizer<A>) -> Minimizer<A>
+    where
+        A::Item: Float,
+    {
+        let standard = Minimizer::<A>::default();
+        minimizer.a = standard.a;
+        minimizer.b = standard.b;
+        minimizer.c = standard.c;
+        minimizer.d = standard.d;
+        minimizer.adaptive = self == Coefficients::Adaptive;
+        if self == Coefficients::GreedyExpansion {
+            minimizer.c = <A::Item as NumCast>::from(3.0).unwrap();
+        }
+        minimizer
+    }
+}
+
+impl<A: Array> Minimizer<A>
+where
+    A::Item: Float,
+{
+    /// Minimizes the function `f` with the seed `x0`, the coefficients
+    /// being chosen by short probe runs.
+    ///
+    /// The probe runs share `fraction` of the evaluation budget, trying
+    /// the standard, adaptive and greedy expansion coefficients from `x0`.
+    /// The remaining budget goes to the coefficients reaching the lowest
+    /// value, from the best point of their probe. Without an evaluation
+    /// limit, the budget is two evaluations per iteration of the limit.
+    /// The output counts the evaluations of the probes.
+    pub fn minimize_tuned<F>(&self, x0: &[A::Item], fraction: f64, mut f: F) -> Result<A>
+    where
+        F: FnMut(&A) -> A::Item,
+    {
+        assert!(
+            fraction > 0.0 && fraction < 1.0,
+            "the probe fraction must be within (0, 1)"
+        );
+
+        let dim = x0.len();
+        let budget = self.max_fev.unwrap_or_else(|| 2 * self.max_iter.limit(dim));
+        let share = (budget as f64 * fraction / Coefficients::ALL.len() as f64) as usize;
+        let share = share.max(dim + 2);
+
+        let mut fev = 0;
+        let mut chosen: Option<(Coefficients, A::Item, A)> = None;
+        for coefficients in Coefficients::ALL {
+            let probe = coefficients.apply(self.clone()).restarts(0).max_fev(share);
+
+            let mut best: Option<(A::Item, A)> = None;
+            // Converged or not, only the best point matters
+            let _ = probe.minimize(x0, |x: &A| {
+                let fx = f(x);
+                fev += 1;
+                if best
+                    .as_ref()
+                    .map_or(!fx.is_nan(), |(f_best, _)| fx < *f_best)
+                {
+                    best = Some((fx, x.clone()));
+                }
+                fx
+            });
+
+            if let Some((f_best, x_best)) = best {
+                if chosen.as_ref().is_none_or(|(_, f_min, _)| f_best < *f_min) {
+                    chosen = Some((coefficients, f_best, x_best));
+                }
+            }
+        }
+
+        let (coefficients, x0) = match chosen {
+            Some((coefficients, _, x)) => (coefficients, x.as_ref().to_vec()),
+            None => (Coefficients::Standard, x0.to_vec()),
+        };
+        coefficients
+            .apply(self.clone())
+            .after(fev, 0, |minimizer| minimizer.minimize(&x0, f))
+    }
+}
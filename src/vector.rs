@@ -33,6 +33,22 @@ impl<A: Array> Vector<A> {
             .for_each(|(x, y)| *x = y.mul_add(a, *x))
     }
 
+    pub(crate) fn dot(&self, rhs: &A) -> A::Item
+    where
+        A::Item: Float,
+    {
+        self.iter()
+            .zip(rhs.as_ref().iter())
+            .fold(A::Item::zero(), |acc, (&x, &y)| x.mul_add(y, acc))
+    }
+
+    pub(crate) fn norm(&self) -> A::Item
+    where
+        A::Item: Float,
+    {
+        self.dot(self).sqrt()
+    }
+
     pub(crate) fn zeros(dim: usize) -> Vector<A>
     where
         A::Item: Zero,
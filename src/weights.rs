@@ -0,0 +1,38 @@
+use num_traits::Float;
+
+use crate::objective::Parameters;
+
+/// Nonnegative weights summing to one, such as mixture weights.
+///
+/// The `n` weights map to `n - 1` unconstrained scalars, their log-ratios
+/// to the last weight, and back through a softmax, so that any simplex
+/// point stays on the probability simplex. Zero weights are taken
+/// as the machine epsilon, their logarithm being finite.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Weights<T>(pub Vec<T>);
+
+impl<T: Float> Parameters<T> for Weights<T> {
+    fn to_scalars(&self) -> Vec<T> {
+        let weights = &self.0;
+        let (&last, rest) = match weights.split_last() {
+            Some(split) => split,
+            None => return Vec::new(),
+        };
+        let last = last.max(T::epsilon()).ln();
+        rest.iter()
+            .map(|&w| w.max(T::epsilon()).ln() - last)
+            .collect()
+    }
+
+    fn from_scalars(x: &[T]) -> Weights<T> {
+        // Shifted by the largest log-ratio, against overflows
+        let max = x.iter().fold(T::zero(), |acc, &xi| acc.max(xi));
+        let exps: Vec<T> = x
+            .iter()
+            .chain(std::iter::once(&T::zero()))
+            .map(|&xi| (xi - max).exp())
+            .collect();
+        let sum = exps.iter().fold(T::zero(), |acc, &e| acc + e);
+        Weights(exps.into_iter().map(|e| e / sum).collect())
+    }
+}